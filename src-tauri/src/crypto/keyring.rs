@@ -43,7 +43,6 @@ fn generate_random_key() -> Vec<u8> {
     key
 }
 
-#[allow(dead_code)]
 pub fn encrypt_password(password: &str, key: &[u8]) -> Result<Vec<u8>, String> {
     if key.len() != 32 {
         return Err("Encryption key must be 32 bytes".to_string());
@@ -66,7 +65,6 @@ pub fn encrypt_password(password: &str, key: &[u8]) -> Result<Vec<u8>, String> {
     Ok(result)
 }
 
-#[allow(dead_code)]
 pub fn decrypt_password(encrypted: &[u8], key: &[u8]) -> Result<String, String> {
     if key.len() != 32 {
         return Err("Encryption key must be 32 bytes".to_string());
@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::Sha256;
+
+use super::keyring::{decrypt_password, encrypt_password};
+
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+/// Encrypted with the derived key so `unlock` can tell a wrong password apart.
+const CHECK_PLAINTEXT: &str = "valo-accounts";
+
+struct UnlockedKey {
+    key: Vec<u8>,
+    last_used: Instant,
+}
+
+/// Key derived from the master password, held only for the current session.
+static UNLOCKED_KEY: Mutex<Option<UnlockedKey>> = Mutex::new(None);
+/// Idle time after which the key is dropped; 0 disables auto-lock.
+static AUTO_LOCK_SECS: AtomicU64 = AtomicU64::new(0);
+/// How often the auto-lock timer checks for an idle key.
+const AUTO_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+pub fn generate_salt() -> Vec<u8> {
+    let mut salt = vec![0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+pub fn derive_key(password: &str, salt: &[u8]) -> Vec<u8> {
+    let mut key = vec![0u8; 32];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, KDF_ITERATIONS, &mut key);
+    key
+}
+
+/// Build the check blob stored alongside the salt for a newly derived key.
+pub fn make_check(key: &[u8]) -> Result<Vec<u8>, String> {
+    encrypt_password(CHECK_PLAINTEXT, key)
+}
+
+/// Whether `key` decrypts `check` to the expected plaintext.
+pub fn verify_key(key: &[u8], check: &[u8]) -> bool {
+    decrypt_password(check, key).is_ok_and(|plain| plain == CHECK_PLAINTEXT)
+}
+
+/// Derive the key for `password` and keep it for the session if it matches `check`.
+pub fn unlock(password: &str, salt: &[u8], check: &[u8]) -> Result<(), String> {
+    let key = derive_key(password, salt);
+    if !verify_key(&key, check) {
+        return Err("Incorrect master password".to_string());
+    }
+    set_key(Some(key));
+    Ok(())
+}
+
+pub fn set_key(key: Option<Vec<u8>>) {
+    *UNLOCKED_KEY.lock().unwrap() = key.map(|key| UnlockedKey {
+        key,
+        last_used: Instant::now(),
+    });
+}
+
+pub fn lock() {
+    set_key(None);
+}
+
+/// Set the idle timeout in minutes; `None` or 0 keeps the key until `lock`.
+pub fn set_auto_lock_minutes(minutes: Option<u32>) {
+    let secs = u64::from(minutes.unwrap_or(0)) * 60;
+    AUTO_LOCK_SECS.store(secs, Ordering::Relaxed);
+}
+
+/// Drop the key if it has been idle longer than the auto-lock timeout.
+fn expire_idle(slot: &mut Option<UnlockedKey>) {
+    let timeout = AUTO_LOCK_SECS.load(Ordering::Relaxed);
+    if timeout == 0 {
+        return;
+    }
+    if slot
+        .as_ref()
+        .is_some_and(|k| k.last_used.elapsed() >= Duration::from_secs(timeout))
+    {
+        log::info!("Auto-lock: master password key expired after inactivity");
+        *slot = None;
+    }
+}
+
+/// Start a background timer that drops the key once it has been idle too long.
+///
+/// Without it the key would only expire on the next access, staying in memory
+/// on an unattended machine.
+pub fn start_auto_lock_timer() {
+    std::thread::spawn(|| loop {
+        std::thread::sleep(AUTO_LOCK_CHECK_INTERVAL);
+        let mut slot = UNLOCKED_KEY.lock().unwrap_or_else(|e| e.into_inner());
+        expire_idle(&mut slot);
+    });
+}
+
+pub fn is_unlocked() -> bool {
+    let mut slot = UNLOCKED_KEY.lock().unwrap();
+    expire_idle(&mut slot);
+    slot.is_some()
+}
+
+/// The session key, or an error asking the user to unlock first.
+///
+/// Each successful call counts as activity and resets the auto-lock timer.
+pub fn unlocked_key() -> Result<Vec<u8>, String> {
+    let mut slot = UNLOCKED_KEY.lock().unwrap();
+    expire_idle(&mut slot);
+    match slot.as_mut() {
+        Some(unlocked) => {
+            unlocked.last_used = Instant::now();
+            Ok(unlocked.key.clone())
+        }
+        None => Err("Passwords are locked; enter the master password".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_accepts_only_the_right_password() {
+        let salt = generate_salt();
+        let key = derive_key("correct horse", &salt);
+        let check = make_check(&key).unwrap();
+
+        assert!(verify_key(&derive_key("correct horse", &salt), &check));
+        assert!(!verify_key(&derive_key("battery staple", &salt), &check));
+    }
+}
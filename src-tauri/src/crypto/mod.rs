@@ -1,2 +1,69 @@
 pub mod dpapi;
 pub mod keyring;
+pub mod master;
+
+/// How stored account passwords are encrypted, persisted as `settings.encryption_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMode {
+    /// Windows DPAPI, tied to the current user account.
+    Machine,
+    /// AES-GCM with a key derived from the user's master password.
+    MasterPassword,
+}
+
+impl EncryptionMode {
+    pub fn from_setting(value: &str) -> Result<Self, String> {
+        match value {
+            "machine" => Ok(Self::Machine),
+            "master_password" => Ok(Self::MasterPassword),
+            other => Err(format!("Unknown encryption mode: {}", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Machine => "machine",
+            Self::MasterPassword => "master_password",
+        }
+    }
+}
+
+/// Encrypt a password for storage using the given mode.
+///
+/// `MasterPassword` fails while the session is locked.
+pub fn protect(password: &str, mode: EncryptionMode) -> Result<Vec<u8>, String> {
+    match mode {
+        EncryptionMode::Machine => dpapi::protect_password(password),
+        EncryptionMode::MasterPassword => {
+            keyring::encrypt_password(password, &master::unlocked_key()?)
+        }
+    }
+}
+
+/// Decrypt a stored password that was encrypted with the given mode.
+pub fn unprotect(encrypted: &[u8], mode: EncryptionMode) -> Result<String, String> {
+    match mode {
+        EncryptionMode::Machine => dpapi::unprotect_password(encrypted),
+        EncryptionMode::MasterPassword => {
+            keyring::decrypt_password(encrypted, &master::unlocked_key()?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_master_password_protect_roundtrip() {
+        master::set_key(Some(vec![7u8; 32]));
+        let encrypted = protect("TestPassword123!", EncryptionMode::MasterPassword).unwrap();
+        assert_eq!(
+            unprotect(&encrypted, EncryptionMode::MasterPassword).unwrap(),
+            "TestPassword123!"
+        );
+
+        master::lock();
+        assert!(unprotect(&encrypted, EncryptionMode::MasterPassword).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use super::{get_connection, models::{Account, UpdateAccount}};
-use crate::crypto::dpapi::protect_password;
+use crate::crypto;
 use crate::fs::create_dir_with_marker;
 use chrono::Local;
 
@@ -10,6 +10,7 @@ pub struct CreateAccountData {
     pub password: Option<String>,
     pub rank: Option<String>,
     pub use_current_data: bool,
+    pub launch_args: Option<String>,
 }
 
 pub fn generate_data_folder_name(account_id: i64) -> String {
@@ -17,6 +18,41 @@ pub fn generate_data_folder_name(account_id: i64) -> String {
     format!("{:03}_{}", account_id, now.format("%Y%m%d%H%M%S"))
 }
 
+/// Store `base` as the account's `data_folder`, appending `_2`, `_3`, ... while
+/// the unique index reports another account already uses the name.
+///
+/// Returns the folder name that was stored.
+fn assign_data_folder_with(
+    conn: &rusqlite::Connection,
+    account_id: i64,
+    base: &str,
+) -> Result<String, String> {
+    const MAX_ATTEMPTS: u32 = 100;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let folder = if attempt == 1 {
+            base.to_string()
+        } else {
+            format!("{}_{}", base, attempt)
+        };
+
+        match conn.execute(
+            "UPDATE accounts SET data_folder = ?1 WHERE id = ?2",
+            (&folder, account_id),
+        ) {
+            Ok(_) => return Ok(folder),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                log::warn!("Data folder {} is already taken, trying another name", folder);
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Err(format!("No free data folder name for account {}", account_id))
+}
+
 pub fn create_account(data: CreateAccountData) -> Result<Account, String> {
     log::info!(
         "Creating new account: {}#{} (use_current_data: {})",
@@ -28,35 +64,30 @@ pub fn create_account(data: CreateAccountData) -> Result<Account, String> {
     let conn = get_connection(None)?;
 
     let encrypted_password = if let Some(ref pw) = data.password {
-        protect_password(pw)?
+        crypto::protect(pw, super::settings::get_encryption_mode()?)?
     } else {
         vec![]
     };
 
     conn.execute(
-        "INSERT INTO accounts (riot_id, tagline, username, encrypted_password, rank, data_folder)
-         VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+        "INSERT INTO accounts (riot_id, tagline, username, encrypted_password, rank, launch_args, data_folder, sort_order)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM accounts))",
         (
             &data.riot_id,
             &data.tagline,
             &data.username,
             &encrypted_password,
             &data.rank,
+            &data.launch_args,
         ),
     )
     .map_err(|e| e.to_string())?;
 
     let id = conn.last_insert_rowid();
-    let generated_folder = generate_data_folder_name(id);
+    let generated_folder = assign_data_folder_with(&conn, id, &generate_data_folder_name(id))?;
 
     log::debug!("Generated folder name: {}", generated_folder);
 
-    conn.execute(
-        "UPDATE accounts SET data_folder = ?1 WHERE id = ?2",
-        (&generated_folder, id),
-    )
-    .map_err(|e| e.to_string())?;
-
     let settings = super::settings::get_settings()?;
     let account_data_path = match settings.account_data_path {
         Some(path) => std::path::PathBuf::from(path),
@@ -102,30 +133,68 @@ pub fn get_all_accounts() -> Result<Vec<Account>, String> {
     let conn = get_connection(None)?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, riot_id, tagline, username, encrypted_password, rank, is_active, data_folder, created_at, updated_at
-             FROM accounts ORDER BY created_at ASC",
-        )
+        .prepare(&format!(
+            "SELECT {} FROM accounts ORDER BY is_favorite DESC, sort_order ASC, created_at ASC",
+            ACCOUNT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let accounts = stmt
+        .query_map([], map_account_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(accounts)
+}
+
+/// Accounts whose `data_folder` is shared with another account, ignoring case.
+///
+/// Rows are grouped by folder, then ordered by id. Only databases created before
+/// the unique index (or a bad import) can hold such rows.
+pub fn find_duplicate_folders() -> Result<Vec<Account>, String> {
+    let conn = get_connection(None)?;
+    find_duplicate_folders_with(&conn)
+}
+
+fn find_duplicate_folders_with(conn: &rusqlite::Connection) -> Result<Vec<Account>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM accounts
+             WHERE lower(data_folder) IN (
+                 SELECT lower(data_folder) FROM accounts
+                  WHERE data_folder IS NOT NULL
+                  GROUP BY lower(data_folder)
+                 HAVING COUNT(*) > 1
+             )
+             ORDER BY lower(data_folder) ASC, id ASC",
+            ACCOUNT_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let accounts = stmt
-        .query_map([], |row| {
-            let encrypted_password: Vec<u8> = row.get(4)?;
-            let has_password = !encrypted_password.is_empty();
-            Ok(Account {
-                id: row.get(0)?,
-                riot_id: row.get(1)?,
-                tagline: row.get(2)?,
-                username: row.get(3)?,
-                encrypted_password,
-                has_password,
-                rank: row.get(5)?,
-                is_active: row.get(6)?,
-                data_folder: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-            })
-        })
+        .query_map([], map_account_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(accounts)
+}
+
+/// Find accounts by Riot ID, ignoring case as the Riot Client does.
+pub fn find_accounts_by_riot_id(riot_id: &str, tagline: &str) -> Result<Vec<Account>, String> {
+    let conn = get_connection(None)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM accounts
+             WHERE riot_id = ?1 COLLATE NOCASE AND tagline = ?2 COLLATE NOCASE
+             ORDER BY created_at ASC",
+            ACCOUNT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let accounts = stmt
+        .query_map([riot_id, tagline], map_account_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -137,7 +206,7 @@ pub fn update_account(data: UpdateAccount) -> Result<Account, String> {
     let conn = get_connection(None)?;
 
     if let Some(ref pw) = data.password {
-        let encrypted = protect_password(pw)?;
+        let encrypted = crypto::protect(pw, super::settings::get_encryption_mode()?)?;
         conn.execute(
             "UPDATE accounts SET riot_id=?1, tagline=?2, username=?3, encrypted_password=?4, rank=?5, updated_at=datetime('now') WHERE id=?6",
             (&data.riot_id, &data.tagline, &data.username, &encrypted, &data.rank, data.id),
@@ -151,9 +220,268 @@ pub fn update_account(data: UpdateAccount) -> Result<Account, String> {
         .map_err(|e| e.to_string())?;
     }
 
+    if let Some(ref args) = data.launch_args {
+        conn.execute(
+            "UPDATE accounts SET launch_args = ?1 WHERE id = ?2",
+            (args, data.id),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
     get_account_by_id(&conn, data.id)
 }
 
+/// Point an account at an absolute data directory outside `account_data_path`.
+///
+/// Passing `None` clears the override so the account uses its `data_folder` again.
+pub fn set_data_path_override(account_id: i64, path: Option<&str>) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    conn.execute(
+        "UPDATE accounts SET data_path_override = ?1 WHERE id = ?2",
+        (path, account_id),
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_account_by_id(&conn, account_id)
+}
+
+/// Record a renamed data folder; the directory itself is renamed by the caller.
+pub fn set_data_folder(account_id: i64, folder: &str) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    conn.execute(
+        "UPDATE accounts SET data_folder = ?1 WHERE id = ?2",
+        (folder, account_id),
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_account_by_id(&conn, account_id)
+}
+
+pub fn set_account_region(account_id: i64, region: Option<&str>) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    conn.execute(
+        "UPDATE accounts SET region = ?1 WHERE id = ?2",
+        (region, account_id),
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_account_by_id(&conn, account_id)
+}
+
+/// Flip an account's favorite flag; favorites are listed first.
+pub fn toggle_favorite(account_id: i64) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    let updated = conn
+        .execute(
+            "UPDATE accounts SET is_favorite = NOT is_favorite WHERE id = ?1",
+            [account_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err(format!("Account {} not found", account_id));
+    }
+
+    get_account_by_id(&conn, account_id)
+}
+
+/// Store the list position of each account in `ordered_ids`, in one transaction.
+///
+/// Ids without a matching account are skipped; accounts left out of
+/// `ordered_ids` keep their current position. Favorites are still listed first.
+pub fn reorder_accounts(ordered_ids: &[i64]) -> Result<(), String> {
+    let mut conn = get_connection(None)?;
+    reorder_with(&mut conn, ordered_ids)
+}
+
+fn reorder_with(conn: &mut rusqlite::Connection, ordered_ids: &[i64]) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    for (position, id) in ordered_ids.iter().enumerate() {
+        let updated = tx
+            .execute(
+                "UPDATE accounts SET sort_order = ?1 WHERE id = ?2",
+                (position as i64, id),
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            log::warn!("reorder_accounts: skipping unknown account {}", id);
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Remove an account's stored password, leaving every other field as is.
+pub fn clear_account_password(account_id: i64) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    clear_password_with(&conn, account_id)
+}
+
+fn clear_password_with(conn: &rusqlite::Connection, account_id: i64) -> Result<Account, String> {
+    let updated = conn
+        .execute(
+            "UPDATE accounts SET encrypted_password = x'', updated_at = datetime('now') WHERE id = ?1",
+            [account_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err(format!("Account {} not found", account_id));
+    }
+
+    log::info!("Cleared stored password for account {}", account_id);
+    get_account_by_id(conn, account_id)
+}
+
+/// Copy fields that `keep_id` lacks from `remove_id`.
+///
+/// Only empty fields on the kept account are filled; when both accounts have a
+/// password the kept account's password wins. The removed row is left untouched.
+pub fn merge_account_fields(keep_id: i64, remove_id: i64) -> Result<Account, String> {
+    let conn = get_connection(None)?;
+    let keep = get_account_by_id(&conn, keep_id)?;
+    let remove = get_account_by_id(&conn, remove_id)?;
+
+    fn pick(keep: Option<String>, remove: Option<String>) -> Option<String> {
+        match keep {
+            Some(v) if !v.is_empty() => Some(v),
+            _ => remove.filter(|v| !v.is_empty()),
+        }
+    }
+
+    let encrypted_password = if keep.encrypted_password.is_empty() {
+        remove.encrypted_password
+    } else {
+        keep.encrypted_password
+    };
+
+    conn.execute(
+        "UPDATE accounts SET username=?1, encrypted_password=?2, rank=?3, region=?4 WHERE id=?5",
+        (
+            pick(keep.username, remove.username),
+            &encrypted_password,
+            pick(keep.rank, remove.rank),
+            pick(keep.region, remove.region),
+            keep_id,
+        ),
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_account_by_id(&conn, keep_id)
+}
+
+/// Delete an account row along with its cached storefront, loadout, owned agents and wishlist.
+///
+/// The caller is responsible for switching away from the account first and for
+/// cleaning up its data folder.
+pub fn delete_account(account_id: i64) -> Result<(), String> {
+    let mut conn = get_connection(None)?;
+    delete_account_with(&mut conn, account_id)?;
+
+    log::info!("Deleted account {}", account_id);
+    Ok(())
+}
+
+/// Remove an account and every per-account row in one transaction.
+///
+/// `PRAGMA foreign_keys` is off, so the schema's `ON DELETE CASCADE` never fires.
+fn delete_account_with(conn: &mut rusqlite::Connection, account_id: i64) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    for table in [
+        "storefront_cache",
+        "storefront_history",
+        "loadout_cache",
+        "owned_agents_cache",
+        "wishlist",
+    ] {
+        tx.execute(&format!("DELETE FROM {} WHERE account_id = ?1", table), [account_id])
+            .map_err(|e| e.to_string())?;
+    }
+    tx.execute(
+        "UPDATE settings SET previous_account_id = NULL WHERE previous_account_id = ?1",
+        [account_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let removed = tx
+        .execute("DELETE FROM accounts WHERE id = ?1", [account_id])
+        .map_err(|e| e.to_string())?;
+
+    if removed == 0 {
+        return Err(format!("Account {} not found", account_id));
+    }
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Re-encrypt every stored password with `reencrypt` and switch `settings.encryption_mode`.
+///
+/// Runs in one transaction so a failure on any account leaves the old scheme intact.
+/// `master_params` is the `(salt, check)` pair for master-password mode, `None` otherwise.
+pub fn reencrypt_all_passwords<F>(
+    mode: crypto::EncryptionMode,
+    master_params: Option<(&[u8], &[u8])>,
+    reencrypt: F,
+) -> Result<usize, String>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+{
+    let mut conn = get_connection(None)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let rows: Vec<(i64, Vec<u8>)> = {
+        let mut stmt = tx
+            .prepare("SELECT id, encrypted_password FROM accounts WHERE length(encrypted_password) > 0")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        rows
+    };
+
+    for (id, encrypted) in &rows {
+        let updated = reencrypt(encrypted)
+            .map_err(|e| format!("Failed to re-encrypt password for account {}: {}", id, e))?;
+        tx.execute(
+            "UPDATE accounts SET encrypted_password = ?1 WHERE id = ?2",
+            (&updated, id),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    let (salt, check) = master_params.unzip();
+    tx.execute(
+        "UPDATE settings SET encryption_mode = ?1, master_password_salt = ?2, master_password_check = ?3 WHERE id = 1",
+        (mode.as_str(), salt, check),
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    log::info!("Re-encrypted {} passwords with mode {}", rows.len(), mode.as_str());
+    Ok(rows.len())
+}
+
+/// Resolve the on-disk data directory for an account.
+///
+/// Uses `data_path_override` when set, otherwise `data_folder` under `account_data_path`.
+pub fn resolve_account_data_dir(
+    account: &Account,
+    account_data_path: &std::path::Path,
+) -> Option<std::path::PathBuf> {
+    if let Some(ref path) = account.data_path_override {
+        return Some(std::path::PathBuf::from(path));
+    }
+    account
+        .data_folder
+        .as_ref()
+        .map(|folder| account_data_path.join(folder))
+}
+
 pub fn is_current_data_available() -> Result<bool, String> {
     let conn = get_connection(None)?;
 
@@ -168,28 +496,184 @@ pub fn is_current_data_available() -> Result<bool, String> {
     Ok(count == 0)
 }
 
+const ACCOUNT_COLUMNS: &str =
+    "id, riot_id, tagline, username, encrypted_password, rank, is_active, data_folder, created_at, updated_at, data_path_override, region, is_favorite, launch_args";
+
+fn map_account_row(row: &rusqlite::Row) -> rusqlite::Result<Account> {
+    let encrypted_password: Vec<u8> = row.get(4)?;
+    let has_password = !encrypted_password.is_empty();
+    Ok(Account {
+        id: row.get(0)?,
+        riot_id: row.get(1)?,
+        tagline: row.get(2)?,
+        username: row.get(3)?,
+        encrypted_password,
+        has_password,
+        rank: row.get(5)?,
+        is_active: row.get(6)?,
+        data_folder: row.get(7)?,
+        data_path_override: row.get(10)?,
+        region: row.get(11)?,
+        is_favorite: row.get(12)?,
+        launch_args: row.get(13)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
 fn get_account_by_id(conn: &rusqlite::Connection, id: i64) -> Result<Account, String> {
     conn.query_row(
-        "SELECT id, riot_id, tagline, username, encrypted_password, rank, is_active, data_folder, created_at, updated_at
-         FROM accounts WHERE id = ?1",
+        &format!("SELECT {} FROM accounts WHERE id = ?1", ACCOUNT_COLUMNS),
         [id],
-        |row| {
-            let encrypted_password: Vec<u8> = row.get(4)?;
-            let has_password = !encrypted_password.is_empty();
-            Ok(Account {
-                id: row.get(0)?,
-                riot_id: row.get(1)?,
-                tagline: row.get(2)?,
-                username: row.get(3)?,
-                encrypted_password,
-                has_password,
-                rank: row.get(5)?,
-                is_active: row.get(6)?,
-                data_folder: row.get(7)?,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-            })
-        },
+        map_account_row,
     )
     .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_data_folders() {
+        let db_path = std::env::temp_dir().join("test_duplicate_data_folders.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = crate::db::init::initialize_database(Some(db_path.clone())).unwrap();
+            for name in ["A", "B", "C"] {
+                conn.execute(
+                    "INSERT INTO accounts (riot_id, tagline, encrypted_password) VALUES (?1, 'JP1', x'')",
+                    [name],
+                )
+                .unwrap();
+            }
+
+            assert_eq!(assign_data_folder_with(&conn, 1, "shared").unwrap(), "shared");
+            assert_eq!(assign_data_folder_with(&conn, 2, "SHARED").unwrap(), "SHARED_2");
+            assert!(find_duplicate_folders_with(&conn).unwrap().is_empty());
+
+            // Databases from before the unique index can still hold duplicates
+            conn.execute("DROP INDEX idx_accounts_data_folder", []).unwrap();
+            conn.execute("UPDATE accounts SET data_folder = 'Shared' WHERE id = 3", []).unwrap();
+            let ids: Vec<i64> = find_duplicate_folders_with(&conn)
+                .unwrap()
+                .iter()
+                .map(|a| a.id)
+                .collect();
+            assert_eq!(ids, vec![1, 3]);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
+    #[test]
+    fn test_delete_account_clears_history() {
+        let db_path = std::env::temp_dir().join("test_delete_account_clears_history.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let mut conn = crate::db::init::initialize_database(Some(db_path.clone())).unwrap();
+            conn.execute(
+                "INSERT INTO accounts (riot_id, tagline, encrypted_password) VALUES ('A', 'JP1', x'')",
+                [],
+            )
+            .unwrap();
+            let id = conn.last_insert_rowid();
+            conn.execute(
+                "INSERT INTO storefront_history (account_id, storefront_json, fetched_at) VALUES (?1, '{}', 0)",
+                [id],
+            )
+            .unwrap();
+
+            // A missing account rolls back, leaving other rows alone
+            conn.execute(
+                "INSERT INTO storefront_history (account_id, storefront_json, fetched_at) VALUES (?1, '{}', 0)",
+                [id + 1],
+            )
+            .unwrap();
+            assert!(delete_account_with(&mut conn, id + 1).is_err());
+            let count = |conn: &rusqlite::Connection| -> i64 {
+                conn.query_row("SELECT COUNT(*) FROM storefront_history", [], |r| r.get(0))
+                    .unwrap()
+            };
+            assert_eq!(count(&conn), 2);
+
+            delete_account_with(&mut conn, id).unwrap();
+            assert_eq!(count(&conn), 1);
+            assert!(get_account_by_id(&conn, id).is_err());
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
+    #[test]
+    fn test_clear_account_password() {
+        let db_path = std::env::temp_dir().join("test_clear_account_password.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = crate::db::init::initialize_database(Some(db_path.clone())).unwrap();
+            conn.execute(
+                "INSERT INTO accounts (riot_id, tagline, username, encrypted_password, rank)
+                 VALUES ('Player', 'JP1', 'login', x'01020304', 'Gold 1')",
+                [],
+            )
+            .unwrap();
+            let id = conn.last_insert_rowid();
+            assert!(get_account_by_id(&conn, id).unwrap().has_password);
+
+            let account = clear_password_with(&conn, id).unwrap();
+            assert!(!account.has_password);
+            assert!(account.encrypted_password.is_empty());
+            assert_eq!(account.username.as_deref(), Some("login"));
+            assert_eq!(account.rank.as_deref(), Some("Gold 1"));
+            assert_eq!(
+                crate::stored_password(&account).unwrap_err(),
+                "No password stored"
+            );
+
+            assert!(clear_password_with(&conn, id + 1).is_err());
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
+    #[test]
+    fn test_reorder_skips_unknown_ids() {
+        let db_path = std::env::temp_dir().join("test_reorder_skips_unknown_ids.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let mut conn = crate::db::init::initialize_database(Some(db_path.clone())).unwrap();
+            for name in ["A", "B", "C"] {
+                conn.execute(
+                    "INSERT INTO accounts (riot_id, tagline, encrypted_password) VALUES (?1, 'JP1', x'')",
+                    [name],
+                )
+                .unwrap();
+            }
+
+            reorder_with(&mut conn, &[3, 99, 1, 2]).unwrap();
+
+            let names: Vec<String> = conn
+                .prepare("SELECT riot_id FROM accounts ORDER BY sort_order ASC")
+                .unwrap()
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(names, vec!["C", "A", "B"]);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use super::models::Account;
+use crate::crypto::{keyring, master};
+
+const BACKUP_VERSION: u32 = 1;
+
+/// One account in a backup file.
+///
+/// `data_folder` and ids are left out: the importing machine creates fresh ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupAccount {
+    pub riot_id: String,
+    pub tagline: String,
+    pub username: Option<String>,
+    pub rank: Option<String>,
+    pub region: Option<String>,
+    pub launch_args: Option<String>,
+    /// Base64 AES-GCM ciphertext under the backup passphrase.
+    pub password: Option<String>,
+}
+
+/// Portable account backup whose passwords are sealed with a passphrase-derived key
+/// (PBKDF2 + AES-GCM) instead of the machine-bound encryption used in data.db.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountBackup {
+    pub version: u32,
+    pub salt: String,
+    /// Lets `open_backup` reject a wrong passphrase before decrypting anything.
+    pub check: String,
+    pub accounts: Vec<BackupAccount>,
+}
+
+/// Build a backup from accounts paired with their decrypted passwords.
+pub fn build_backup(
+    accounts: &[(Account, Option<String>)],
+    passphrase: &str,
+) -> Result<AccountBackup, String> {
+    if passphrase.is_empty() {
+        return Err("Backup passphrase must not be empty".to_string());
+    }
+
+    let salt = master::generate_salt();
+    let key = master::derive_key(passphrase, &salt);
+
+    let accounts = accounts
+        .iter()
+        .map(|(account, password)| {
+            let password = password
+                .as_deref()
+                .map(|p| keyring::encrypt_password(p, &key))
+                .transpose()?
+                .map(|sealed| general_purpose::STANDARD.encode(sealed));
+            Ok(BackupAccount {
+                riot_id: account.riot_id.clone(),
+                tagline: account.tagline.clone(),
+                username: account.username.clone(),
+                rank: account.rank.clone(),
+                region: account.region.clone(),
+                launch_args: account.launch_args.clone(),
+                password,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(AccountBackup {
+        version: BACKUP_VERSION,
+        salt: general_purpose::STANDARD.encode(&salt),
+        check: general_purpose::STANDARD.encode(master::make_check(&key)?),
+        accounts,
+    })
+}
+
+/// Unseal a backup, returning each account with its plaintext password.
+pub fn open_backup(
+    backup: AccountBackup,
+    passphrase: &str,
+) -> Result<Vec<(BackupAccount, Option<String>)>, String> {
+    if backup.version != BACKUP_VERSION {
+        return Err(format!("Unsupported backup version {}", backup.version));
+    }
+
+    let decode = |value: &str| {
+        general_purpose::STANDARD
+            .decode(value)
+            .map_err(|e| format!("Corrupt backup: {}", e))
+    };
+
+    let key = master::derive_key(passphrase, &decode(&backup.salt)?);
+    if !master::verify_key(&key, &decode(&backup.check)?) {
+        return Err("Incorrect backup passphrase".to_string());
+    }
+
+    backup
+        .accounts
+        .into_iter()
+        .map(|account| {
+            let password = account
+                .password
+                .as_deref()
+                .map(|sealed| keyring::decrypt_password(&decode(sealed)?, &key))
+                .transpose()?;
+            Ok((account, password))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(riot_id: &str) -> Account {
+        Account {
+            id: 1,
+            riot_id: riot_id.to_string(),
+            tagline: "JP1".to_string(),
+            username: Some("login".to_string()),
+            encrypted_password: vec![1, 2, 3],
+            has_password: true,
+            rank: None,
+            is_active: false,
+            data_folder: Some("001_20240101000000".to_string()),
+            data_path_override: None,
+            region: Some("ap".to_string()),
+            is_favorite: false,
+            launch_args: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let accounts = vec![
+            (account("Player"), Some("hunter2".to_string())),
+            (account("NoPassword"), None),
+        ];
+        let backup = build_backup(&accounts, "passphrase").unwrap();
+
+        let json = serde_json::to_string(&backup).unwrap();
+        assert!(!json.contains("hunter2"));
+        assert!(!json.contains("001_20240101000000"));
+
+        let opened = open_backup(serde_json::from_str(&json).unwrap(), "passphrase").unwrap();
+        assert_eq!(opened.len(), 2);
+        assert_eq!(opened[0].0.riot_id, "Player");
+        assert_eq!(opened[0].1.as_deref(), Some("hunter2"));
+        assert_eq!(opened[1].1, None);
+
+        let err = open_backup(serde_json::from_str(&json).unwrap(), "wrong").unwrap_err();
+        assert_eq!(err, "Incorrect backup passphrase");
+    }
+}
@@ -1,13 +1,140 @@
 use rusqlite::{Connection, Result};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use serde::Serialize;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
 use super::accounts;
 
 const SCHEMA_SQL: &str = include_str!("schema.sql");
 
+/// File next to the executable naming the directory that holds the databases.
+const DB_LOCATION_FILE: &str = "db_location.txt";
+
 static DB_PATH: Mutex<Option<String>> = Mutex::new(None);
 
-pub fn get_default_db_path() -> Result<PathBuf, String> {
+/// Idle data.db connections, tagged with the path they were opened on.
+///
+/// A pool rather than one locked connection: callers routinely read settings
+/// while holding another connection, which would deadlock on a single slot.
+static IDLE_CONNECTIONS: Mutex<Vec<(String, Connection)>> = Mutex::new(Vec::new());
+
+/// Number of pooled connections currently checked out, signalled whenever one is returned.
+///
+/// `move_database` waits for this to reach zero so no caller is left writing to the old file.
+static CHECKED_OUT: (Mutex<usize>, Condvar) = (Mutex::new(0), Condvar::new());
+
+/// Most idle connections kept open; the oldest is closed to make room.
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+/// How long a connection waits on another writer before failing with `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A data.db connection from `get_connection`; returned to the idle pool on drop.
+pub struct DbConnection {
+    /// Set for pooled connections, `None` for one-off connections to an explicit path.
+    pool_path: Option<String>,
+    conn: Option<Connection>,
+}
+
+impl Deref for DbConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection already released")
+    }
+}
+
+impl DerefMut for DbConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection already released")
+    }
+}
+
+impl Drop for DbConnection {
+    fn drop(&mut self) {
+        let (Some(path), Some(conn)) = (self.pool_path.take(), self.conn.take()) else {
+            return;
+        };
+        let _checked_in = CheckedIn;
+        // Never hand out a connection with a transaction left open
+        if !conn.is_autocommit() {
+            return;
+        }
+        let mut idle = IDLE_CONNECTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if idle.len() >= MAX_IDLE_CONNECTIONS {
+            idle.remove(0);
+        }
+        idle.push((path, conn));
+    }
+}
+
+/// Marks a pooled connection as returned once the idle pool has been updated.
+struct CheckedIn;
+
+impl Drop for CheckedIn {
+    fn drop(&mut self) {
+        let (count, returned) = &CHECKED_OUT;
+        let mut count = count.lock().unwrap_or_else(|e| e.into_inner());
+        *count = count.saturating_sub(1);
+        returned.notify_all();
+    }
+}
+
+/// Wait up to `BUSY_TIMEOUT` for every checked-out pooled connection to be returned.
+///
+/// Only meaningful while holding `DB_PATH`, which stops new connections being handed out.
+fn wait_for_checked_out_connections() -> Result<(), String> {
+    let (count, returned) = &CHECKED_OUT;
+    let count = count.lock().unwrap_or_else(|e| e.into_inner());
+    let (count, _) = returned
+        .wait_timeout_while(count, BUSY_TIMEOUT, |count| *count > 0)
+        .unwrap_or_else(|e| e.into_inner());
+    if *count > 0 {
+        return Err("Database is busy; try again once pending writes finish".to_string());
+    }
+    Ok(())
+}
+
+/// Close the idle connections to `path`, e.g. before that file is moved or recreated.
+fn close_idle_connections(path: &str) {
+    IDLE_CONNECTIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(open_path, _)| open_path != path);
+}
+
+/// Take an idle connection to `path`, or open a new one.
+///
+/// Called with `DB_PATH` held, so a move can't start between the lookup and the checkout.
+fn pooled_connection(path: &str) -> Result<DbConnection, String> {
+    let reused = {
+        let mut idle = IDLE_CONNECTIONS.lock().unwrap_or_else(|e| e.into_inner());
+        idle.iter()
+            .rposition(|(open_path, _)| open_path == path)
+            .map(|i| idle.remove(i).1)
+    };
+
+    let conn = match reused {
+        Some(conn) => conn,
+        None => open_connection(path)?,
+    };
+    *CHECKED_OUT.0.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+
+    Ok(DbConnection {
+        pool_path: Some(path.to_string()),
+        conn: Some(conn),
+    })
+}
+
+fn open_connection(path: &str) -> Result<Connection, String> {
+    let conn = Connection::open(path)
+        .map_err(|e| format!("Failed to open database connection: {}", e))?;
+    conn.busy_timeout(BUSY_TIMEOUT).map_err(|e| e.to_string())?;
+    Ok(conn)
+}
+
+fn get_exe_dir() -> Result<PathBuf, String> {
     let exe_path = std::env::current_exe()
         .map_err(|e| format!("Failed to get executable path: {}", e))?;
 
@@ -15,7 +142,96 @@ pub fn get_default_db_path() -> Result<PathBuf, String> {
         .parent()
         .ok_or("Failed to get executable directory")?;
 
-    Ok(exe_dir.join("data.db"))
+    Ok(exe_dir.to_path_buf())
+}
+
+/// Directory holding data.db and skins.db.
+///
+/// This is the directory named in `db_location.txt` when present, otherwise
+/// the executable's own directory.
+pub fn get_db_dir() -> Result<PathBuf, String> {
+    let exe_dir = get_exe_dir()?;
+
+    match std::fs::read_to_string(exe_dir.join(DB_LOCATION_FILE)) {
+        Ok(contents) if !contents.trim().is_empty() => Ok(PathBuf::from(contents.trim())),
+        _ => Ok(exe_dir),
+    }
+}
+
+pub fn get_default_db_path() -> Result<PathBuf, String> {
+    Ok(get_db_dir()?.join("data.db"))
+}
+
+/// Record `dir` as the database directory for future launches.
+///
+/// Pointing back at the executable's directory removes the location file.
+fn set_db_dir(dir: &Path) -> Result<(), String> {
+    let exe_dir = get_exe_dir()?;
+    let location_file = exe_dir.join(DB_LOCATION_FILE);
+
+    if dir == exe_dir {
+        if location_file.exists() {
+            std::fs::remove_file(&location_file)
+                .map_err(|e| format!("Failed to remove {}: {}", DB_LOCATION_FILE, e))?;
+        }
+        return Ok(());
+    }
+
+    std::fs::write(&location_file, dir.to_string_lossy().as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", DB_LOCATION_FILE, e))
+}
+
+/// Write a consistent copy of the SQLite database at `src` to `dest`.
+///
+/// Fails without copying if another connection holds a write lock, so a
+/// write in flight is never split between the old and new file.
+pub fn copy_sqlite_database(src: &Path, dest: &Path) -> Result<(), String> {
+    if dest.exists() {
+        return Err(format!("{} already exists", dest.display()));
+    }
+
+    let conn = Connection::open(src)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.busy_timeout(Duration::ZERO)
+        .map_err(|e| e.to_string())?;
+
+    conn.execute_batch("BEGIN IMMEDIATE; COMMIT;")
+        .map_err(|_| "Database is busy; try again once pending writes finish".to_string())?;
+
+    conn.execute("VACUUM INTO ?1", [dest.to_string_lossy().as_ref()])
+        .map_err(|e| format!("Failed to copy database to {}: {}", dest.display(), e))?;
+
+    Ok(())
+}
+
+/// Move data.db into `new_dir` and point all future connections at it.
+///
+/// Pooled connections are keyed by path, so swapping `DB_PATH` is enough for
+/// the rest of the app to pick up the new file. Fails if a connection is still
+/// checked out after `BUSY_TIMEOUT`. Returns the new database path.
+pub fn move_database(new_dir: &Path) -> Result<PathBuf, String> {
+    // Held for the whole move so no new connection is handed out mid-copy
+    let mut db_path = DB_PATH.lock().unwrap();
+    let current = PathBuf::from(db_path.clone().ok_or("Database not initialized")?);
+    let target = new_dir.join("data.db");
+
+    if current == target {
+        return Ok(target);
+    }
+
+    // A connection checked out before the move could otherwise keep writing to the old file
+    wait_for_checked_out_connections()?;
+    copy_sqlite_database(&current, &target)?;
+    set_db_dir(new_dir)?;
+    *db_path = Some(target.to_string_lossy().to_string());
+    close_idle_connections(&current.to_string_lossy());
+
+    if let Err(e) = std::fs::remove_file(&current) {
+        log::warn!("Failed to remove old database {}: {}", current.display(), e);
+    }
+
+    log::info!("Moved database: {} -> {}", current.display(), target.display());
+    Ok(target)
 }
 
 
@@ -24,10 +240,15 @@ pub fn initialize_database(db_path: Option<PathBuf>) -> Result<Connection, Strin
     let path = db_path.unwrap_or(default_path);
     let path_str = path.to_string_lossy().to_string();
     *DB_PATH.lock().unwrap() = Some(path_str.clone());
+    close_idle_connections(&path_str);
 
     let conn = Connection::open(&path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
 
+    // Persistent in the file; lets the process monitor read while a write is in progress
+    conn.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to enable WAL: {}", e))?;
+
     conn.execute_batch(SCHEMA_SQL)
         .map_err(|e| format!("Failed to initialize schema: {}", e))?;
 
@@ -77,6 +298,38 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         [],
     );
 
+    let _ = conn.execute(
+        "ALTER TABLE accounts ADD COLUMN data_path_override TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE accounts ADD COLUMN region TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE accounts ADD COLUMN is_favorite BOOLEAN NOT NULL DEFAULT 0",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE accounts ADD COLUMN launch_args TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE accounts ADD COLUMN sort_order INTEGER",
+        [],
+    );
+
+    // Accounts that predate the column keep their creation order.
+    conn.execute(
+        "UPDATE accounts SET sort_order = id WHERE sort_order IS NULL",
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+
     let _ = conn.execute(
         "ALTER TABLE accounts RENAME COLUMN email TO username",
         [],
@@ -102,6 +355,76 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         [],
     );
 
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN previous_account_id INTEGER",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN auto_select_last_account INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN prefetch_shops_on_startup INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN show_vp_costs INTEGER NOT NULL DEFAULT 1",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN encryption_mode TEXT NOT NULL DEFAULT 'machine'",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN master_password_salt BLOB",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN master_password_check BLOB",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN auto_lock_minutes INTEGER",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN theme TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN accent_color TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN compress_history INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN bundle_discount_alert_threshold REAL",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN process_poll_secs INTEGER NOT NULL DEFAULT 2",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE settings ADD COLUMN extra_watched_processes TEXT",
+        [],
+    );
+
     let _ = conn.execute(
         "ALTER TABLE storefront_cache ADD COLUMN nm_expires_at INTEGER",
         [],
@@ -112,8 +435,28 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         [],
     );
 
+    let _ = conn.execute(
+        "ALTER TABLE storefront_cache ADD COLUMN accessory_store_json TEXT",
+        [],
+    );
+
+    let _ = conn.execute(
+        "ALTER TABLE storefront_cache ADD COLUMN accessory_expires_at INTEGER",
+        [],
+    );
+
     migrate_existing_accounts(conn)?;
 
+    // Kept out of schema.sql: an existing database with duplicate folders would
+    // fail to open. `find_duplicate_folders` lists them for repair.
+    if let Err(e) = conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_data_folder
+         ON accounts(data_folder COLLATE NOCASE)",
+        [],
+    ) {
+        log::warn!("Could not add unique index on accounts.data_folder: {}", e);
+    }
+
     Ok(())
 }
 
@@ -163,24 +506,161 @@ fn migrate_existing_accounts(conn: &Connection) -> Result<(), String> {
     Ok(())
 }
 
-pub fn get_connection(db_path: Option<&str>) -> Result<Connection, String> {
-    let path = match db_path {
-        Some(p) => p.to_string(),
-        None => {
-            DB_PATH.lock().unwrap()
-                .clone()
-                .unwrap_or_else(|| ":memory:".to_string())
+/// Connect to data.db, reusing an idle connection when one is available.
+///
+/// `Some(path)` always opens a fresh, unpooled connection to that file.
+pub fn get_connection(db_path: Option<&str>) -> Result<DbConnection, String> {
+    if let Some(path) = db_path {
+        return Ok(DbConnection {
+            pool_path: None,
+            conn: Some(open_connection(path)?),
+        });
+    }
+
+    let path = DB_PATH.lock().unwrap();
+    match path.as_deref() {
+        Some(path) => pooled_connection(path),
+        // Uninitialized: every in-memory connection is a separate database, so never pool it
+        None => get_connection(Some(":memory:")),
+    }
+}
+
+/// Tables whose columns `verify_db_schema` compares against `schema.sql`.
+const VERIFIED_TABLES: &[&str] = &["accounts", "settings", "storefront_cache"];
+
+/// Columns of one table that differ between the live database and `schema.sql`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TableDrift {
+    pub table: String,
+    /// In `schema.sql` but not in the database, e.g. after an interrupted migration.
+    pub missing: Vec<String>,
+    /// In the database but not in `schema.sql`, e.g. after a manual edit.
+    pub unexpected: Vec<String>,
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .map_err(|e| e.to_string())?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(columns)
+}
+
+/// Compare `conn`'s verified tables against a fresh copy of `schema.sql`.
+///
+/// Only tables with differences are returned. A missing table reports all of
+/// its columns as missing.
+pub fn schema_drift(conn: &Connection) -> Result<Vec<TableDrift>, String> {
+    let expected_conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    expected_conn
+        .execute_batch(SCHEMA_SQL)
+        .map_err(|e| format!("Failed to load bundled schema: {}", e))?;
+
+    let mut drift = Vec::new();
+    for table in VERIFIED_TABLES {
+        let expected = table_columns(&expected_conn, table)?;
+        let actual = table_columns(conn, table)?;
+
+        let missing: Vec<String> =
+            expected.iter().filter(|c| !actual.contains(c)).cloned().collect();
+        let unexpected: Vec<String> =
+            actual.iter().filter(|c| !expected.contains(c)).cloned().collect();
+
+        if !missing.is_empty() || !unexpected.is_empty() {
+            drift.push(TableDrift {
+                table: table.to_string(),
+                missing,
+                unexpected,
+            });
         }
-    };
+    }
+    Ok(drift)
+}
 
-    Connection::open(&path)
-        .map_err(|e| format!("Failed to open database connection: {}", e))
+/// Check the live data.db against `schema.sql`; see `schema_drift`.
+pub fn verify_db_schema() -> Result<Vec<TableDrift>, String> {
+    let conn = get_connection(None)?;
+    let drift = schema_drift(&conn)?;
+    if drift.is_empty() {
+        log::info!("Schema check: data.db matches the bundled schema");
+    } else {
+        log::warn!("Schema check: drift found: {:?}", drift);
+    }
+    Ok(drift)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_schema_drift() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA_SQL).unwrap();
+        assert!(schema_drift(&conn).unwrap().is_empty());
+
+        conn.execute_batch(
+            "ALTER TABLE settings DROP COLUMN theme;
+             ALTER TABLE accounts ADD COLUMN nickname TEXT;",
+        )
+        .unwrap();
+
+        let drift = schema_drift(&conn).unwrap();
+        assert_eq!(
+            drift,
+            vec![
+                TableDrift {
+                    table: "accounts".to_string(),
+                    missing: vec![],
+                    unexpected: vec!["nickname".to_string()],
+                },
+                TableDrift {
+                    table: "settings".to_string(),
+                    missing: vec!["theme".to_string()],
+                    unexpected: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pooled_connection_is_reused() {
+        let db_path = std::env::temp_dir().join("test_pooled_connection.db");
+        let path = db_path.to_string_lossy().to_string();
+        let idle_for_path = || {
+            IDLE_CONNECTIONS
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(p, _)| *p == path)
+                .count()
+        };
+
+        {
+            let first = pooled_connection(&path).unwrap();
+            first
+                .execute_batch("CREATE TABLE IF NOT EXISTS t (x INTEGER); PRAGMA user_version = 7;")
+                .unwrap();
+            // Nested use while one is held opens a second connection instead of blocking
+            let second = pooled_connection(&path).unwrap();
+            let version: i64 = second.query_row("PRAGMA user_version", [], |r| r.get(0)).unwrap();
+            assert_eq!(version, 7);
+        }
+        assert_eq!(idle_for_path(), 2);
+
+        {
+            let _reused = pooled_connection(&path).unwrap();
+            assert_eq!(idle_for_path(), 1);
+        }
+
+        close_idle_connections(&path);
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
     #[test]
     fn test_initialize_database() {
         let temp_dir = std::env::temp_dir();
@@ -203,6 +683,11 @@ mod tests {
 
             assert!(tables.contains(&"accounts".to_string()));
             assert!(tables.contains(&"settings".to_string()));
+
+            let journal_mode: String = conn
+                .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(journal_mode, "wal");
         }
 
         std::fs::remove_file(&db_path).unwrap();
@@ -1,9 +1,12 @@
 pub mod accounts;
+pub mod backup;
 pub mod init;
 pub mod models;
 pub mod settings;
+pub mod wishlist;
 
 pub use accounts::{create_account, get_account, get_all_accounts, is_current_data_available, update_account, CreateAccountData};
 pub use init::{get_connection, initialize_database};
 pub use models::{NewAccount, Settings, UpdateAccount, UpdateSettings};
 pub use settings::{get_settings, update_settings};
+pub use wishlist::{add_wishlist_item, get_wishlist, remove_wishlist_item};
@@ -13,10 +13,40 @@ pub struct Account {
     pub rank: Option<String>,
     pub is_active: bool,
     pub data_folder: Option<String>,
+    pub data_path_override: Option<String>,
+    pub region: Option<String>,
+    pub is_favorite: bool,
+    /// Extra Riot Client arguments for this account; see `process::parse_launch_args`.
+    pub launch_args: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Shareable view of an account for `export_account_list`.
+///
+/// Deliberately excludes every secret and local detail: no password (encrypted
+/// or not), no data folder, and no ids.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountListEntry {
+    pub riot_id: String,
+    pub tagline: String,
+    pub username: Option<String>,
+    pub rank: Option<String>,
+    pub region: Option<String>,
+}
+
+impl From<&Account> for AccountListEntry {
+    fn from(account: &Account) -> Self {
+        Self {
+            riot_id: account.riot_id.clone(),
+            tagline: account.tagline.clone(),
+            username: account.username.clone(),
+            rank: account.rank.clone(),
+            region: account.region.clone(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountWithPassword {
@@ -39,18 +69,33 @@ pub struct NewAccount {
     pub password: Option<String>,
     pub rank: Option<String>,
     pub use_current_data: bool,
+    #[serde(default)]
+    pub launch_args: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub id: i64,
     pub active_account_id: Option<i64>,
+    pub previous_account_id: Option<i64>,
     pub riot_client_service_path: Option<String>,
     pub riot_client_data_path: Option<String>,
     pub account_data_path: Option<String>,
     pub henrikdev_api_key: Option<String>,
     pub region: Option<String>,
     pub launched: bool,
+    pub auto_select_last_account: bool,
+    pub prefetch_shops_on_startup: bool,
+    pub show_vp_costs: bool,
+    pub encryption_mode: String,
+    pub auto_lock_minutes: Option<u32>,
+    pub theme: Option<String>,
+    pub accent_color: Option<String>,
+    pub compress_history: bool,
+    pub bundle_discount_alert_threshold: Option<f64>,
+    pub process_poll_secs: u32,
+    /// Executables watched by the process monitor besides Riot Client and Valorant.
+    pub extra_watched_processes: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -63,6 +108,9 @@ pub struct UpdateAccount {
     pub username: Option<String>,
     pub password: Option<String>,
     pub rank: Option<String>,
+    /// `None` keeps the stored arguments; an empty string clears them.
+    #[serde(default)]
+    pub launch_args: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,4 +121,58 @@ pub struct UpdateSettings {
     pub account_data_path: Option<String>,
     pub henrikdev_api_key: Option<String>,
     pub region: Option<String>,
+    pub auto_select_last_account: Option<bool>,
+    pub prefetch_shops_on_startup: Option<bool>,
+    pub show_vp_costs: Option<bool>,
+    /// `Some(0)` turns auto-lock off (stored as NULL); `None` keeps the stored timeout.
+    pub auto_lock_minutes: Option<u32>,
+    pub theme: Option<String>,
+    pub accent_color: Option<String>,
+    pub compress_history: Option<bool>,
+    pub bundle_discount_alert_threshold: Option<f64>,
+    /// Turn bundle discount alerts off; takes precedence over `bundle_discount_alert_threshold`.
+    #[serde(default)]
+    pub clear_bundle_discount_alert_threshold: bool,
+    pub process_poll_secs: Option<u32>,
+    pub extra_watched_processes: Option<Vec<String>>,
+}
+
+/// Accounts whose stored yaml reports the same Riot PUUID.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateAccountGroup {
+    pub puuid: String,
+    pub accounts: Vec<Account>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_list_entry_excludes_secrets() {
+        let account = Account {
+            id: 1,
+            riot_id: "Player".to_string(),
+            tagline: "JP1".to_string(),
+            username: Some("login".to_string()),
+            encrypted_password: vec![1, 2, 3],
+            has_password: true,
+            rank: Some("Gold 2".to_string()),
+            is_active: true,
+            data_folder: Some("001_20240101000000".to_string()),
+            data_path_override: Some("D:\\Data".to_string()),
+            region: Some("ap".to_string()),
+            is_favorite: false,
+            launch_args: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+
+        let json = serde_json::to_value(AccountListEntry::from(&account)).unwrap();
+        let keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+
+        assert!(keys.iter().all(|k| !k.contains("password")));
+        assert!(keys.iter().all(|k| !k.contains("data_")));
+        assert_eq!(json["riot_id"], "Player");
+    }
 }
@@ -10,6 +10,11 @@ CREATE TABLE IF NOT EXISTS accounts (
     rank TEXT,
     is_active BOOLEAN DEFAULT 0,
     data_folder TEXT,
+    data_path_override TEXT,
+    region TEXT,
+    is_favorite BOOLEAN NOT NULL DEFAULT 0,
+    launch_args TEXT,
+    sort_order INTEGER,
     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
     updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
 );
@@ -18,12 +23,26 @@ CREATE TABLE IF NOT EXISTS accounts (
 CREATE TABLE IF NOT EXISTS settings (
     id INTEGER PRIMARY KEY CHECK (id = 1),
     active_account_id INTEGER,
+    previous_account_id INTEGER,
     riot_client_service_path TEXT,
     riot_client_data_path TEXT,
     account_data_path TEXT,
     henrikdev_api_key TEXT,
     region TEXT,
     launched INTEGER NOT NULL DEFAULT 0,
+    auto_select_last_account INTEGER NOT NULL DEFAULT 0,
+    prefetch_shops_on_startup INTEGER NOT NULL DEFAULT 0,
+    show_vp_costs INTEGER NOT NULL DEFAULT 1,
+    encryption_mode TEXT NOT NULL DEFAULT 'machine',
+    auto_lock_minutes INTEGER,
+    theme TEXT,
+    accent_color TEXT,
+    compress_history INTEGER NOT NULL DEFAULT 0,
+    bundle_discount_alert_threshold REAL,
+    process_poll_secs INTEGER NOT NULL DEFAULT 2,
+    extra_watched_processes TEXT,
+    master_password_salt BLOB,
+    master_password_check BLOB,
     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
     updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
 
@@ -57,10 +76,55 @@ CREATE TABLE IF NOT EXISTS storefront_cache (
     bundles_json TEXT,
     expires_at INTEGER NOT NULL,
     nm_expires_at INTEGER,
+    accessory_store_json TEXT,
+    accessory_expires_at INTEGER,
     cached_at DATETIME DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
 );
 
+-- Loadout cache (per-account, short-lived)
+CREATE TABLE IF NOT EXISTS loadout_cache (
+    account_id INTEGER PRIMARY KEY,
+    loadout_json TEXT NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+-- Storefront history (one row per live fetch, kept for export)
+CREATE TABLE IF NOT EXISTS storefront_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    storefront_json TEXT NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_storefront_history_account ON storefront_history(account_id);
+
+-- Owned agents cache (per-account entitlement UUIDs)
+CREATE TABLE IF NOT EXISTS owned_agents_cache (
+    account_id INTEGER PRIMARY KEY,
+    agents_json TEXT NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+-- Skin wishlist (per account, matched against the daily shop)
+CREATE TABLE IF NOT EXISTS wishlist (
+    account_id INTEGER NOT NULL,
+    skin_uuid TEXT NOT NULL,
+    added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (account_id, skin_uuid),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+-- Featured bundles already alerted on, with the time their offer ends
+CREATE TABLE IF NOT EXISTS alerted_bundles (
+    bundle_name TEXT NOT NULL,
+    expires_at INTEGER NOT NULL,
+    PRIMARY KEY (bundle_name, expires_at)
+);
+
 -- Initial settings record
 INSERT OR IGNORE INTO settings (id)
 VALUES (1);
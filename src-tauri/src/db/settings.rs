@@ -1,4 +1,5 @@
 use super::{get_connection, models::Settings, models::UpdateSettings};
+use crate::crypto::EncryptionMode;
 use std::path::PathBuf;
 
 pub fn get_settings() -> Result<Settings, String> {
@@ -6,7 +7,7 @@ pub fn get_settings() -> Result<Settings, String> {
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, active_account_id, riot_client_service_path, riot_client_data_path, account_data_path, henrikdev_api_key, region, launched, created_at, updated_at
+            "SELECT id, active_account_id, riot_client_service_path, riot_client_data_path, account_data_path, henrikdev_api_key, region, launched, created_at, updated_at, previous_account_id, auto_select_last_account, prefetch_shops_on_startup, show_vp_costs, encryption_mode, auto_lock_minutes, theme, accent_color, compress_history, bundle_discount_alert_threshold, process_poll_secs, extra_watched_processes
              FROM settings
              WHERE id = 1",
         )
@@ -17,12 +18,27 @@ pub fn get_settings() -> Result<Settings, String> {
             Ok(Settings {
                 id: row.get(0)?,
                 active_account_id: row.get(1)?,
+                previous_account_id: row.get(10)?,
                 riot_client_service_path: row.get(2)?,
                 riot_client_data_path: row.get(3)?,
                 account_data_path: row.get(4)?,
                 henrikdev_api_key: row.get(5)?,
                 region: row.get(6)?,
                 launched: row.get::<_, i64>(7)? != 0,
+                auto_select_last_account: row.get::<_, i64>(11)? != 0,
+                prefetch_shops_on_startup: row.get::<_, i64>(12)? != 0,
+                show_vp_costs: row.get::<_, i64>(13)? != 0,
+                encryption_mode: row.get(14)?,
+                auto_lock_minutes: row.get(15)?,
+                theme: row.get(16)?,
+                accent_color: row.get(17)?,
+                compress_history: row.get::<_, i64>(18)? != 0,
+                bundle_discount_alert_threshold: row.get(19)?,
+                process_poll_secs: row.get(20)?,
+                extra_watched_processes: row
+                    .get::<_, Option<String>>(21)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
                 created_at: row.get(8)?,
                 updated_at: row.get(9)?,
             })
@@ -35,6 +51,37 @@ pub fn get_settings() -> Result<Settings, String> {
 pub fn update_settings(update: UpdateSettings) -> Result<Settings, String> {
     let conn = get_connection(None)?;
 
+    if let Some(ref color) = update.accent_color {
+        if !is_hex_color(color) {
+            return Err(format!("Invalid accent color '{}': expected #RGB or #RRGGBB", color));
+        }
+    }
+
+    if let Some(threshold) = update.bundle_discount_alert_threshold {
+        if !(0.0..=100.0).contains(&threshold) {
+            return Err(format!(
+                "Invalid bundle discount alert threshold {}: expected 0-100",
+                threshold
+            ));
+        }
+    }
+
+    if update.process_poll_secs == Some(0) {
+        return Err("Process poll interval must be at least 1 second".to_string());
+    }
+
+    if let Some(ref names) = update.extra_watched_processes {
+        if let Some(name) = names.iter().find(|n| !is_process_name(n)) {
+            return Err(format!("Invalid process name '{}': expected e.g. VALORANT.exe", name));
+        }
+    }
+    let extra_watched_processes = update
+        .extra_watched_processes
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
     let prev_settings = get_settings()?;
 
     if update.riot_client_data_path.is_some() || update.account_data_path.is_some() {
@@ -76,7 +123,18 @@ pub fn update_settings(update: UpdateSettings) -> Result<Settings, String> {
              riot_client_data_path = COALESCE(?2, riot_client_data_path),
              account_data_path = COALESCE(?3, account_data_path),
              henrikdev_api_key = COALESCE(?4, henrikdev_api_key),
-             region = COALESCE(?5, region)
+             region = COALESCE(?5, region),
+             auto_select_last_account = COALESCE(?6, auto_select_last_account),
+             prefetch_shops_on_startup = COALESCE(?7, prefetch_shops_on_startup),
+             show_vp_costs = COALESCE(?8, show_vp_costs),
+             auto_lock_minutes = CASE WHEN ?9 = 0 THEN NULL ELSE COALESCE(?9, auto_lock_minutes) END,
+             theme = COALESCE(?10, theme),
+             accent_color = COALESCE(?11, accent_color),
+             compress_history = COALESCE(?12, compress_history),
+             bundle_discount_alert_threshold = CASE WHEN ?16 THEN NULL
+                 ELSE COALESCE(?13, bundle_discount_alert_threshold) END,
+             process_poll_secs = COALESCE(?14, process_poll_secs),
+             extra_watched_processes = COALESCE(?15, extra_watched_processes)
          WHERE id = 1",
         (
             &update.riot_client_service_path,
@@ -84,9 +142,87 @@ pub fn update_settings(update: UpdateSettings) -> Result<Settings, String> {
             &update.account_data_path,
             &update.henrikdev_api_key,
             &update.region,
+            &update.auto_select_last_account,
+            &update.prefetch_shops_on_startup,
+            &update.show_vp_costs,
+            &update.auto_lock_minutes,
+            &update.theme,
+            &update.accent_color,
+            &update.compress_history,
+            &update.bundle_discount_alert_threshold,
+            &update.process_poll_secs,
+            &extra_watched_processes,
+            &update.clear_bundle_discount_alert_threshold,
         ),
     )
     .map_err(|e| e.to_string())?;
 
     get_settings()
 }
+
+/// Accepts `#RGB` and `#RRGGBB` hex colors.
+fn is_hex_color(value: &str) -> bool {
+    match value.strip_prefix('#') {
+        Some(hex) => {
+            (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
+
+/// Accepts bare executable names such as `VALORANT.exe`.
+///
+/// The name is embedded in a WMI query, so quotes and path separators are rejected.
+fn is_process_name(value: &str) -> bool {
+    let split = value.len().saturating_sub(4);
+    match (value.get(..split), value.get(split..)) {
+        (Some(stem), Some(ext)) if !stem.is_empty() && ext.eq_ignore_ascii_case(".exe") => stem
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ')),
+        _ => false,
+    }
+}
+
+pub fn get_encryption_mode() -> Result<EncryptionMode, String> {
+    EncryptionMode::from_setting(&get_settings()?.encryption_mode)
+}
+
+/// The stored `(salt, check)` pair for master-password mode, if one is set.
+pub fn get_master_password_params() -> Result<Option<(Vec<u8>, Vec<u8>)>, String> {
+    let conn = get_connection(None)?;
+
+    let (salt, check): (Option<Vec<u8>>, Option<Vec<u8>>) = conn
+        .query_row(
+            "SELECT master_password_salt, master_password_check FROM settings WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(salt.zip(check))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_hex_color() {
+        assert!(is_hex_color("#fff"));
+        assert!(is_hex_color("#FF4655"));
+        assert!(!is_hex_color("FF4655"));
+        assert!(!is_hex_color("#FF465"));
+        assert!(!is_hex_color("#GG4655"));
+        assert!(!is_hex_color("red"));
+    }
+
+    #[test]
+    fn test_is_process_name() {
+        assert!(is_process_name("VALORANT.exe"));
+        assert!(is_process_name("VALORANT-Win64-Shipping.EXE"));
+        assert!(!is_process_name(".exe"));
+        assert!(!is_process_name("VALORANT"));
+        assert!(!is_process_name("x' OR Name LIKE '%.exe"));
+        assert!(!is_process_name(r"C:\Riot\VALORANT.exe"));
+    }
+}
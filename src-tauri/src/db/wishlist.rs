@@ -0,0 +1,87 @@
+use rusqlite::Connection;
+
+use super::get_connection;
+
+/// Add a skin to an account's wishlist. Adding a skin twice is a no-op.
+pub fn add_wishlist_item(account_id: i64, skin_uuid: &str) -> Result<(), String> {
+    let conn = get_connection(None)?;
+    add_item_with(&conn, account_id, skin_uuid)
+}
+
+/// Remove a skin from an account's wishlist. Removing a missing skin is a no-op.
+pub fn remove_wishlist_item(account_id: i64, skin_uuid: &str) -> Result<(), String> {
+    let conn = get_connection(None)?;
+    remove_item_with(&conn, account_id, skin_uuid)
+}
+
+/// The account's wishlisted skin UUIDs, oldest first.
+pub fn get_wishlist(account_id: i64) -> Result<Vec<String>, String> {
+    let conn = get_connection(None)?;
+    wishlist_with(&conn, account_id)
+}
+
+fn add_item_with(conn: &Connection, account_id: i64, skin_uuid: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR IGNORE INTO wishlist (account_id, skin_uuid) VALUES (?1, ?2)",
+        (account_id, skin_uuid.to_lowercase()),
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn remove_item_with(conn: &Connection, account_id: i64, skin_uuid: &str) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM wishlist WHERE account_id = ?1 AND skin_uuid = ?2",
+        (account_id, skin_uuid.to_lowercase()),
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn wishlist_with(conn: &Connection, account_id: i64) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT skin_uuid FROM wishlist
+              WHERE account_id = ?1
+              ORDER BY added_at ASC, rowid ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let uuids = stmt
+        .query_map([account_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(uuids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wishlist_add_remove() {
+        let db_path = std::env::temp_dir().join("test_wishlist_add_remove.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = crate::db::init::initialize_database(Some(db_path.clone())).unwrap();
+
+            add_item_with(&conn, 1, "AAAA-skin").unwrap();
+            add_item_with(&conn, 1, "aaaa-skin").unwrap();
+            add_item_with(&conn, 1, "bbbb-skin").unwrap();
+            add_item_with(&conn, 2, "cccc-skin").unwrap();
+            assert_eq!(wishlist_with(&conn, 1).unwrap(), vec!["aaaa-skin", "bbbb-skin"]);
+
+            remove_item_with(&conn, 1, "AAAA-skin").unwrap();
+            remove_item_with(&conn, 1, "missing").unwrap();
+            assert_eq!(wishlist_with(&conn, 1).unwrap(), vec!["bbbb-skin"]);
+            assert_eq!(wishlist_with(&conn, 2).unwrap(), vec!["cccc-skin"]);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+}
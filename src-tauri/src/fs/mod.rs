@@ -1,13 +1,19 @@
+use serde::Serialize;
 use std::ffi::OsStr;
 use std::fs;
 use std::io;
 use std::os::windows::ffi::OsStrExt;
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
-use winapi::um::fileapi::{CreateFileW, GetFileAttributesW, INVALID_FILE_ATTRIBUTES, OPEN_EXISTING};
+use winapi::um::fileapi::{
+    CreateFileW, GetDriveTypeW, GetFileAttributesW, INVALID_FILE_ATTRIBUTES, OPEN_EXISTING,
+};
 use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
 use winapi::um::ioapiset::DeviceIoControl;
-use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+use winapi::um::winbase::{
+    DRIVE_CDROM, DRIVE_FIXED, DRIVE_RAMDISK, DRIVE_REMOTE, DRIVE_REMOVABLE,
+    FILE_FLAG_BACKUP_SEMANTICS,
+};
 use winapi::um::winioctl::FSCTL_GET_REPARSE_POINT;
 use winapi::um::winnt::{
     FILE_ATTRIBUTE_REPARSE_POINT, FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, HANDLE,
@@ -15,6 +21,9 @@ use winapi::um::winnt::{
 };
 
 const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA0000003;
+const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000000C;
+/// `ERROR_PRIVILEGE_NOT_HELD`, returned when symlink creation needs elevation.
+const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
 
 #[repr(C)]
 struct ReparseDataBuffer {
@@ -108,6 +117,47 @@ pub fn create_junction(link: &Path, target: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Link `link` to `target`, preferring a junction.
+///
+/// When the junction fails and `target` is on a different drive than `link`
+/// (e.g. a network share), a directory symlink is created instead. Symlinks
+/// need Developer Mode or elevation; without either a descriptive error is returned.
+pub fn create_link(link: &Path, target: &Path) -> Result<(), String> {
+    let junction_err = match create_junction(link, target) {
+        Ok(()) => return Ok(()),
+        Err(e) => e,
+    };
+
+    if on_same_volume(link, target) {
+        return Err(junction_err);
+    }
+
+    log::warn!(
+        "Junction failed for cross-drive target, falling back to a directory symlink: {}",
+        junction_err
+    );
+    std::os::windows::fs::symlink_dir(target, link).map_err(|e| {
+        if e.raw_os_error() == Some(ERROR_PRIVILEGE_NOT_HELD) {
+            format!(
+                "{} is on a different drive and needs a symbolic link, which Windows only allows \
+                 with Developer Mode enabled (Settings > System > For developers) or when running \
+                 as administrator",
+                target.display()
+            )
+        } else {
+            format!(
+                "Failed to create symlink from {} to {}: {}",
+                link.display(),
+                target.display(),
+                e
+            )
+        }
+    })?;
+
+    log::info!("Symlink created successfully: {} -> {}", link.display(), target.display());
+    Ok(())
+}
+
 /// Remove a junction point
 pub fn remove_junction(link: &Path) -> Result<(), String> {
     log::debug!("Removing junction: {}", link.display());
@@ -192,6 +242,14 @@ pub fn get_junction_target(link: &Path) -> Result<PathBuf, String> {
 
         let reparse_data = &*(buffer.as_ptr() as *const ReparseDataBuffer);
 
+        // Directory symlinks (see `create_link`) use a different buffer layout
+        if reparse_data.reparse_tag == IO_REPARSE_TAG_SYMLINK {
+            let target = fs::read_link(link)
+                .map_err(|e| format!("Failed to read symlink target: {}", e))?;
+            let target = target.to_string_lossy();
+            return Ok(PathBuf::from(target.strip_prefix(r"\\?\").unwrap_or(&target)));
+        }
+
         if reparse_data.reparse_tag != IO_REPARSE_TAG_MOUNT_POINT {
             return Err("Path is not a mount point".to_string());
         }
@@ -217,6 +275,78 @@ pub fn get_junction_target(link: &Path) -> Result<PathBuf, String> {
     }
 }
 
+/// Whether the current user can create directory symlinks without elevation.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymlinkPrivilegeStatus {
+    pub developer_mode: bool,
+    pub has_symlink_privilege: bool,
+    pub can_create_symlinks: bool,
+    pub remediation: Option<String>,
+}
+
+const DEVELOPER_MODE_KEY: &str =
+    r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\AppModelUnlock";
+
+/// Check whether Windows Developer Mode is enabled via the AppModelUnlock registry key
+fn is_developer_mode_enabled() -> bool {
+    let output = std::process::Command::new("reg")
+        .args(["query", DEVELOPER_MODE_KEY, "/v", "AllowDevelopmentWithoutDevLicense"])
+        .creation_flags(0x08000000)
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter(|line| line.contains("AllowDevelopmentWithoutDevLicense"))
+            .any(|line| line.split_whitespace().last() == Some("0x1")),
+        _ => false,
+    }
+}
+
+/// Check whether the process token holds SeCreateSymbolicLinkPrivilege
+fn has_symlink_privilege() -> bool {
+    let output = std::process::Command::new("whoami")
+        .arg("/priv")
+        .creation_flags(0x08000000)
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            String::from_utf8_lossy(&out.stdout).contains("SeCreateSymbolicLinkPrivilege")
+        }
+        _ => false,
+    }
+}
+
+/// Report whether unprivileged directory symlink creation is allowed
+pub fn check_symlink_privilege() -> SymlinkPrivilegeStatus {
+    let developer_mode = is_developer_mode_enabled();
+    let has_symlink_privilege = has_symlink_privilege();
+    let can_create_symlinks = developer_mode || has_symlink_privilege;
+
+    log::debug!(
+        "Symlink privilege check: developer_mode={}, privilege={}",
+        developer_mode,
+        has_symlink_privilege
+    );
+
+    let remediation = if can_create_symlinks {
+        None
+    } else {
+        Some(
+            "Enable Developer Mode in Settings > System > For developers, or run the app as administrator"
+                .to_string(),
+        )
+    };
+
+    SymlinkPrivilegeStatus {
+        developer_mode,
+        has_symlink_privilege,
+        can_create_symlinks,
+        remediation,
+    }
+}
+
 /// Create directory and place a marker file with the same name as the directory
 /// This is useful for debugging to verify which directory is being used
 pub fn create_dir_with_marker(dir_path: &Path) -> Result<(), String> {
@@ -236,6 +366,281 @@ pub fn create_dir_with_marker(dir_path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Whether `name` can be used as a single Windows folder name.
+///
+/// Rejects path separators, the characters `<>:"|?*`, control characters,
+/// trailing dots or spaces, `.`/`..`, and reserved device names like `CON` or `COM1`.
+pub fn is_valid_folder_name(name: &str) -> bool {
+    const RESERVED: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+
+    if name.is_empty() || name == "." || name == ".." || name.ends_with(['.', ' ']) {
+        return false;
+    }
+    if name
+        .chars()
+        .any(|c| c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+    {
+        return false;
+    }
+
+    let stem = name.split('.').next().unwrap_or(name).trim_end().to_ascii_uppercase();
+    let is_device = RESERVED.contains(&stem.as_str())
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem[3..].chars().all(|c| c.is_ascii_digit() && c != '0'));
+    !is_device
+}
+
+/// Rename a folder made by `create_dir_with_marker`, renaming its marker to match.
+///
+/// A missing marker is not an error; the folder is still renamed.
+pub fn rename_dir_with_marker(from: &Path, to: &Path) -> Result<(), String> {
+    log::info!("Renaming directory: {} -> {}", from.display(), to.display());
+
+    fs::rename(from, to).map_err(|e| format!("Failed to rename directory: {}", e))?;
+
+    if let (Some(old_name), Some(new_name)) = (from.file_name(), to.file_name()) {
+        let old_marker = to.join(old_name);
+        if old_marker.is_file() {
+            fs::rename(&old_marker, to.join(new_name))
+                .map_err(|e| format!("Failed to rename marker file: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create and remove a throwaway junction inside `scratch_dir` to prove junctions work there.
+pub fn test_junction_capability(scratch_dir: &Path) -> Result<(), String> {
+    let suffix = std::process::id();
+    let target = scratch_dir.join(format!(".valo_junction_target_{}", suffix));
+    let link = scratch_dir.join(format!(".valo_junction_link_{}", suffix));
+
+    fs::create_dir_all(&target)
+        .map_err(|e| format!("Failed to create test directory: {}", e))?;
+
+    let result = create_junction(&link, &target).and_then(|_| {
+        let linked = is_symlink(&link)?;
+        remove_junction(&link)?;
+        if linked {
+            Ok(())
+        } else {
+            Err(format!("{} was created but is not a junction", link.display()))
+        }
+    });
+
+    if let Err(e) = fs::remove_dir(&target) {
+        log::warn!("Failed to remove junction test directory {}: {}", target.display(), e);
+    }
+
+    result
+}
+
+/// Whether two paths live on the same drive or share, judged by their path prefix.
+pub fn on_same_volume(a: &Path, b: &Path) -> bool {
+    let prefix = |p: &Path| match p.components().next() {
+        Some(std::path::Component::Prefix(prefix)) => {
+            Some(prefix.as_os_str().to_string_lossy().to_lowercase())
+        }
+        _ => None,
+    };
+    prefix(a) == prefix(b)
+}
+
+/// Kind of drive a path lives on, as reported by `GetDriveTypeW`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriveType {
+    Fixed,
+    Removable,
+    Remote,
+    CdRom,
+    RamDisk,
+    Unknown,
+}
+
+impl DriveType {
+    /// Drives that can go away while a junction still points at them.
+    pub fn is_detachable(self) -> bool {
+        matches!(self, DriveType::Removable | DriveType::Remote)
+    }
+}
+
+/// Look up the drive type of the volume `path` is on. Relative paths are `Unknown`.
+pub fn drive_type(path: &Path) -> DriveType {
+    let mut root = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::Prefix(_) | std::path::Component::RootDir => root.push(component),
+            _ => break,
+        }
+    }
+    if root.as_os_str().is_empty() {
+        return DriveType::Unknown;
+    }
+
+    // GetDriveTypeW wants the root with a trailing backslash ("C:\", "\\server\share\")
+    let mut wide: Vec<u16> = root.as_os_str().encode_wide().collect();
+    if wide.last() != Some(&(b'\\' as u16)) {
+        wide.push(b'\\' as u16);
+    }
+    wide.push(0);
+
+    match unsafe { GetDriveTypeW(wide.as_ptr()) } {
+        DRIVE_FIXED => DriveType::Fixed,
+        DRIVE_REMOVABLE => DriveType::Removable,
+        DRIVE_REMOTE => DriveType::Remote,
+        DRIVE_CDROM => DriveType::CdRom,
+        DRIVE_RAMDISK => DriveType::RamDisk,
+        _ => DriveType::Unknown,
+    }
+}
+
+/// Whether a directory accepts new files, with the OS error when it does not.
+#[derive(Debug, Clone, Serialize)]
+pub struct WritableStatus {
+    pub writable: bool,
+    pub error: Option<String>,
+}
+
+/// Probe `dir_path` by creating and deleting a temp file in it.
+///
+/// A directory that does not exist yet is probed at its nearest existing
+/// ancestor, since that is where `create_dir_all` would have to write.
+pub fn check_dir_writable(dir_path: &Path) -> WritableStatus {
+    let probe_dir = match dir_path.ancestors().find(|p| p.is_dir()) {
+        Some(dir) => dir,
+        None => {
+            return WritableStatus {
+                writable: false,
+                error: Some(format!("No existing parent directory for {}", dir_path.display())),
+            }
+        }
+    };
+
+    let probe = probe_dir.join(format!(".valo_write_probe_{}", std::process::id()));
+    let result = fs::write(&probe, b"").and_then(|_| fs::remove_file(&probe));
+
+    log::debug!("Write probe in {}: {:?}", probe_dir.display(), result);
+
+    match result {
+        Ok(()) => WritableStatus {
+            writable: true,
+            error: None,
+        },
+        Err(e) => WritableStatus {
+            writable: false,
+            error: Some(format!("Cannot write to {}: {}", probe_dir.display(), e)),
+        },
+    }
+}
+
+/// Total size in bytes of all files under `path`.
+///
+/// Entries that cannot be read are skipped, and links are not followed so a
+/// junction back into the tree cannot loop.
+pub fn dir_size(path: &Path) -> u64 {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::debug!("dir_size: skipping {}: {}", path.display(), e);
+            return 0;
+        }
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match fs::symlink_metadata(entry.path()) {
+            Ok(meta) if meta.file_type().is_symlink() => 0,
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Every directory under `root` (excluding `root` itself) that is a reparse point.
+///
+/// Reparse points are reported but never descended into, and the walk stops
+/// `max_depth` levels below `root`. Unreadable directories are skipped.
+pub fn find_reparse_points(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    collect_reparse_points(root, max_depth, &mut found);
+    found.sort();
+    found
+}
+
+fn collect_reparse_points(dir: &Path, depth_left: usize, found: &mut Vec<PathBuf>) {
+    if depth_left == 0 {
+        return;
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::debug!("find_reparse_points: skipping {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if is_symlink(&path).unwrap_or(false) {
+            found.push(path);
+        } else if path.is_dir() {
+            collect_reparse_points(&path, depth_left - 1, found);
+        }
+    }
+}
+
+/// Result of checking a data folder for its marker file.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MarkerStatus {
+    /// The marker named after the folder is present.
+    Ok,
+    /// The folder itself does not exist.
+    FolderMissing,
+    /// No marker file was found.
+    MarkerMissing,
+    /// Marker files exist but none matches the expected name.
+    Mismatched { found: Vec<String> },
+}
+
+/// Check that `dir_path` holds the marker written by `create_dir_with_marker`
+/// for a folder named `expected`.
+///
+/// Markers are recognized as empty files without an extension.
+pub fn check_dir_marker(dir_path: &Path, expected: &str) -> Result<MarkerStatus, String> {
+    if !dir_path.is_dir() {
+        return Ok(MarkerStatus::FolderMissing);
+    }
+
+    if dir_path.join(expected).is_file() {
+        return Ok(MarkerStatus::Ok);
+    }
+
+    let entries = fs::read_dir(dir_path)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir_path.display(), e))?;
+
+    let mut found: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let path = entry.path();
+            path.extension().is_none()
+                && entry.metadata().is_ok_and(|m| m.is_file() && m.len() == 0)
+        })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    found.sort();
+
+    if found.is_empty() {
+        Ok(MarkerStatus::MarkerMissing)
+    } else {
+        Ok(MarkerStatus::Mismatched { found })
+    }
+}
+
 /// Move all contents from source directory to destination directory
 /// Uses copy-verify-delete pattern to prevent data loss
 pub fn move_directory_contents(src: &Path, dest: &Path) -> Result<(), String> {
@@ -351,7 +756,7 @@ pub fn move_directory_contents(src: &Path, dest: &Path) -> Result<(), String> {
 }
 
 /// Helper function to recursively copy a directory
-fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
+pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
     fs::create_dir_all(dest).map_err(|e| {
         format!(
             "Failed to create directory {}: {}",
@@ -392,6 +797,122 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_is_valid_folder_name() {
+        assert!(is_valid_folder_name("main_account"));
+        assert!(is_valid_folder_name("001_20240101000000"));
+        assert!(is_valid_folder_name("Smurf (EU)"));
+        assert!(is_valid_folder_name("COM10"));
+
+        assert!(!is_valid_folder_name(""));
+        assert!(!is_valid_folder_name(".."));
+        assert!(!is_valid_folder_name("a/b"));
+        assert!(!is_valid_folder_name("a\\b"));
+        assert!(!is_valid_folder_name("what?"));
+        assert!(!is_valid_folder_name("trailing."));
+        assert!(!is_valid_folder_name("con"));
+        assert!(!is_valid_folder_name("LPT1.txt"));
+    }
+
+    #[test]
+    fn test_rename_dir_with_marker() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("001_20240101000000");
+        let to = temp_dir.path().join("main");
+        create_dir_with_marker(&from).unwrap();
+        fs::write(from.join("data.yaml"), "x").unwrap();
+
+        rename_dir_with_marker(&from, &to).unwrap();
+
+        assert!(!from.exists());
+        assert_eq!(check_dir_marker(&to, "main").unwrap(), MarkerStatus::Ok);
+        assert!(!to.join("001_20240101000000").exists());
+        assert!(to.join("data.yaml").is_file());
+    }
+
+    #[test]
+    fn test_dir_size_sums_nested_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(temp_dir.path().join("one"), [0u8; 10]).unwrap();
+        fs::write(nested.join("two"), [0u8; 32]).unwrap();
+
+        assert_eq!(dir_size(temp_dir.path()), 42);
+        assert_eq!(dir_size(&temp_dir.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn test_find_reparse_points() {
+        let temp_dir = TempDir::new().unwrap();
+        let real = temp_dir.path().join("real");
+        fs::create_dir_all(real.join("nested")).unwrap();
+        let link = temp_dir.path().join("account").join("link");
+        fs::create_dir_all(link.parent().unwrap()).unwrap();
+        create_junction(&link, &real).unwrap();
+
+        assert_eq!(find_reparse_points(temp_dir.path(), 3), vec![link.clone()]);
+        assert!(find_reparse_points(temp_dir.path(), 1).is_empty());
+
+        remove_junction(&link).unwrap();
+    }
+
+    #[test]
+    fn test_on_same_volume() {
+        assert!(on_same_volume(Path::new(r"C:\Riot\Data"), Path::new(r"c:\Accounts")));
+        assert!(!on_same_volume(Path::new(r"C:\Riot\Data"), Path::new(r"D:\Accounts")));
+    }
+
+    #[test]
+    fn test_junction_capability_cleans_up() {
+        let temp_dir = TempDir::new().unwrap();
+        test_junction_capability(temp_dir.path()).unwrap();
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_check_dir_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        let status = check_dir_writable(temp_dir.path());
+        assert!(status.writable);
+        assert!(status.error.is_none());
+
+        let not_yet_created = temp_dir.path().join("a").join("b");
+        assert!(check_dir_writable(&not_yet_created).writable);
+        assert!(!not_yet_created.exists());
+
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_check_dir_marker() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("001_20240101000000");
+        create_dir_with_marker(&dir).unwrap();
+        fs::write(dir.join("RiotGamesPrivateSettings.yaml"), "x").unwrap();
+
+        assert_eq!(
+            check_dir_marker(&dir, "001_20240101000000").unwrap(),
+            MarkerStatus::Ok
+        );
+        assert_eq!(
+            check_dir_marker(&dir, "002_20240101000000").unwrap(),
+            MarkerStatus::Mismatched {
+                found: vec!["001_20240101000000".to_string()]
+            }
+        );
+        assert_eq!(
+            check_dir_marker(&temp_dir.path().join("missing"), "missing").unwrap(),
+            MarkerStatus::FolderMissing
+        );
+
+        fs::remove_file(dir.join("001_20240101000000")).unwrap();
+        assert_eq!(
+            check_dir_marker(&dir, "001_20240101000000").unwrap(),
+            MarkerStatus::MarkerMissing
+        );
+    }
+
     #[test]
     fn test_create_and_remove_junction() {
         let temp_dir = TempDir::new().unwrap();
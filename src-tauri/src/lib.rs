@@ -16,6 +16,27 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::Manager;
 
 static DEMO_MODE: AtomicBool = AtomicBool::new(false);
+static SWITCH_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+/// Set in `setup`, for events raised outside a command, such as during a switch.
+static APP_HANDLE: std::sync::OnceLock<tauri::AppHandle> = std::sync::OnceLock::new();
+
+/// Held for the duration of an account switch; releases the flag on drop.
+struct SwitchGuard;
+
+impl SwitchGuard {
+    fn acquire() -> Result<Self, String> {
+        SWITCH_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| SwitchGuard)
+            .map_err(|_| "Account switch already in progress".to_string())
+    }
+}
+
+impl Drop for SwitchGuard {
+    fn drop(&mut self) {
+        SWITCH_IN_PROGRESS.store(false, Ordering::Release);
+    }
+}
 
 #[tauri::command]
 fn is_demo_mode() -> bool {
@@ -66,6 +87,12 @@ fn get_valorant_status() -> bool {
     process::check_valorant_running()
 }
 
+/// Re-check both processes now instead of waiting for the next monitor tick.
+#[tauri::command]
+fn refresh_process_status(app: tauri::AppHandle) -> process::ProcessStatus {
+    process::refresh_process_status(&app)
+}
+
 #[tauri::command]
 fn kill_riot_client() -> Result<(), String> {
     process::kill_riot_client()
@@ -76,6 +103,18 @@ fn launch_riot_client() -> Result<(), String> {
     process::launch_riot_client()
 }
 
+/// Launch VALORANT directly; fails when the game is already running.
+#[tauri::command]
+fn launch_valorant() -> Result<(), String> {
+    process::launch_valorant()
+}
+
+/// Check a Riot Client service path before it is saved in settings.
+#[tauri::command]
+fn validate_riot_client_path(path: String) -> process::RiotClientPathStatus {
+    process::validate_riot_client_path(&PathBuf::from(path))
+}
+
 #[tauri::command]
 fn get_app_settings() -> Result<Settings, String> {
     get_settings().map_err(|e| e.to_string())
@@ -83,11 +122,17 @@ fn get_app_settings() -> Result<Settings, String> {
 
 #[tauri::command]
 fn update_app_settings(settings: UpdateSettings) -> Result<Settings, String> {
-    update_settings(settings)
+    let updated = update_settings(settings)?;
+    crypto::master::set_auto_lock_minutes(updated.auto_lock_minutes);
+    Ok(updated)
 }
 
 #[tauri::command]
 fn add_account(account: NewAccount) -> Result<db::models::Account, String> {
+    if let Some(ref args) = account.launch_args {
+        process::parse_launch_args(args)?;
+    }
+
     let use_current_data = account.use_current_data;
     let data = CreateAccountData {
         riot_id: account.riot_id,
@@ -96,6 +141,7 @@ fn add_account(account: NewAccount) -> Result<db::models::Account, String> {
         password: account.password,
         rank: account.rank,
         use_current_data,
+        launch_args: account.launch_args,
     };
 
     let created = create_account(data)?;
@@ -115,6 +161,9 @@ fn list_accounts() -> Result<Vec<db::models::Account>, String> {
 
 #[tauri::command]
 fn edit_account(account: UpdateAccount) -> Result<db::models::Account, String> {
+    if let Some(ref args) = account.launch_args {
+        process::parse_launch_args(args)?;
+    }
     update_account(account)
 }
 
@@ -131,84 +180,204 @@ fn mark_launched() -> Result<(), String> {
     Ok(())
 }
 
-fn perform_account_switch(account_id: Option<i64>) -> Result<(), String> {
-    let settings = get_settings()?;
+/// Resolve the base directory that holds every account's data folder.
+fn account_data_root(settings: &Settings) -> Result<PathBuf, String> {
+    match settings.account_data_path {
+        Some(ref path) => Ok(PathBuf::from(path)),
+        None => db::init::get_default_account_data_path(),
+    }
+}
 
-    let riot_data_path = match settings.riot_client_data_path {
-        Some(path) => PathBuf::from(path),
-        None => db::init::get_default_riot_client_data_path()?,
-    };
+/// Resolve the Riot Client data directory that the junction replaces.
+fn riot_data_root(settings: &Settings) -> Result<PathBuf, String> {
+    match settings.riot_client_data_path {
+        Some(ref path) => Ok(PathBuf::from(path)),
+        None => db::init::get_default_riot_client_data_path(),
+    }
+}
 
-    let account_data_path = match settings.account_data_path {
-        Some(path) => PathBuf::from(path),
-        None => db::init::get_default_account_data_path()?,
-    };
+/// One filesystem or database operation of an account switch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind")]
+enum SwitchStep {
+    /// Assign a generated `data_folder` to an account that has none.
+    AssignDataFolder { account_id: i64, folder: String },
+    /// Create the target folder with its marker file.
+    CreateTargetDir { path: String },
+    /// Remove the existing junction at the Riot data path.
+    RemoveJunction { path: String },
+    /// Move a plain Riot data directory's contents into the target.
+    MoveContents { from: String, to: String },
+    /// Remove the then-empty Riot data directory.
+    RemoveDir { path: String },
+    /// Best-effort `rmdir` for anything left at the Riot data path, such as a broken junction.
+    ForceRemove { path: String },
+    /// Create the junction (or symlink) at the Riot data path.
+    CreateJunction { link: String, target: String },
+    /// Record the new active account and the one switched away from.
+    SetActiveAccount { account_id: Option<i64>, previous_account_id: Option<i64> },
+}
 
-    log::debug!("Riot data path: {}", riot_data_path.display());
-    log::debug!("Account data path: {}", account_data_path.display());
+/// Work out the steps that switch the junction to `account_id`, touching nothing.
+///
+/// Returns the target folder and the steps in execution order.
+fn plan_account_switch(
+    settings: &Settings,
+    account_id: Option<i64>,
+) -> Result<(PathBuf, Vec<SwitchStep>), String> {
+    let riot_data_path = riot_data_root(settings)?;
+    let account_data_path = account_data_root(settings)?;
+    let mut steps = Vec::new();
 
     let target = if let Some(id) = account_id {
-        let account = get_account(id)?;
-        let data_folder = account
-            .data_folder
-            .ok_or("Account has no data directory assigned")?;
-        log::info!("Switching to account {} ({})", id, data_folder);
-        account_data_path.join(data_folder)
+        let mut account = get_account(id)?;
+        if account.data_folder.is_none() {
+            let folder = db::accounts::generate_data_folder_name(id);
+            steps.push(SwitchStep::AssignDataFolder { account_id: id, folder: folder.clone() });
+            account.data_folder = Some(folder);
+        }
+        db::accounts::resolve_account_data_dir(&account, &account_data_path)
+            .ok_or("Account has no data directory assigned")?
     } else {
-        log::info!("Switching to unselected state");
         account_data_path.join("_unselected")
     };
 
-    log::debug!("Target directory: {}", target.display());
-
     if !target.exists() {
-        log::info!("Creating target directory: {}", target.display());
-        fs::create_dir_with_marker(&target)?;
+        steps.push(SwitchStep::CreateTargetDir { path: path_string(&target) });
     }
 
-    // Force cleanup of any existing path (junction, directory, or broken link)
-    // Use Windows rmdir command for robust removal
-    log::info!("Cleaning up riot data path if it exists: {}", riot_data_path.display());
+    let is_link = fs::is_symlink(&riot_data_path).unwrap_or(false);
+    if is_link {
+        steps.push(SwitchStep::RemoveJunction { path: path_string(&riot_data_path) });
+    } else if riot_data_path.is_dir() {
+        steps.push(SwitchStep::MoveContents {
+            from: path_string(&riot_data_path),
+            to: path_string(&target),
+        });
+        steps.push(SwitchStep::RemoveDir { path: path_string(&riot_data_path) });
+    }
+
+    steps.push(SwitchStep::ForceRemove { path: path_string(&riot_data_path) });
+    steps.push(SwitchStep::CreateJunction {
+        link: path_string(&riot_data_path),
+        target: path_string(&target),
+    });
+
+    let previous_account_id = settings
+        .active_account_id
+        .filter(|_| settings.active_account_id != account_id);
+    steps.push(SwitchStep::SetActiveAccount { account_id, previous_account_id });
+
+    Ok((target, steps))
+}
 
-    // First, try to detect and handle the existing path
-    let path_exists = riot_data_path.exists() || fs::is_symlink(&riot_data_path).unwrap_or(false);
+fn path_string(path: &std::path::Path) -> String {
+    path.to_string_lossy().to_string()
+}
 
-    if path_exists {
-        if fs::is_symlink(&riot_data_path).unwrap_or(false) {
+/// Carry out one planned switch step.
+fn run_switch_step(step: &SwitchStep) -> Result<(), String> {
+    match step {
+        SwitchStep::AssignDataFolder { account_id, folder } => {
+            log::warn!("Account {} has no data folder, assigning {}", account_id, folder);
+            db::accounts::set_data_folder(*account_id, folder)?;
+        }
+        SwitchStep::CreateTargetDir { path } => {
+            log::info!("Creating target directory: {}", path);
+            fs::create_dir_with_marker(std::path::Path::new(path))?;
+        }
+        SwitchStep::RemoveJunction { path } => {
             log::info!("Detected junction point, removing");
-            fs::remove_junction(&riot_data_path)?;
-        } else if riot_data_path.is_dir() {
+            fs::remove_junction(std::path::Path::new(path))?;
+        }
+        SwitchStep::MoveContents { from, to } => {
             log::info!("Detected regular directory, moving contents to target");
-            fs::move_directory_contents(&riot_data_path, &target)?;
-            std::fs::remove_dir(&riot_data_path)
+            fs::move_directory_contents(std::path::Path::new(from), std::path::Path::new(to))?;
+        }
+        SwitchStep::RemoveDir { path } => {
+            std::fs::remove_dir(path)
                 .map_err(|e| format!("Failed to remove directory: {}", e))?;
         }
+        SwitchStep::ForceRemove { path } => {
+            // Force remove anything that might still exist (including broken junctions)
+            // This is safe because we've already moved any real data
+            let output = std::process::Command::new("cmd")
+                .args(["/C", "rmdir", path])
+                .creation_flags(0x08000000)
+                .output();
+
+            if let Ok(out) = output {
+                if out.status.success() {
+                    log::info!("Successfully cleaned up path with rmdir");
+                }
+            }
+        }
+        SwitchStep::CreateJunction { link, target } => {
+            log::info!("Creating junction: {} -> {}", link, target);
+            fs::create_link(std::path::Path::new(link), std::path::Path::new(target))?;
+        }
+        SwitchStep::SetActiveAccount { account_id, previous_account_id } => {
+            let conn = db::init::get_connection(None)?;
+            conn.execute(
+                "UPDATE settings SET active_account_id = ?1 WHERE id = 1",
+                [account_id],
+            )
+            .map_err(|e| e.to_string())?;
+
+            // Remember the account we switched away from for `switch_to_previous`
+            if previous_account_id.is_some() {
+                log::debug!("Recording previous account: {:?}", previous_account_id);
+                conn.execute(
+                    "UPDATE settings SET previous_account_id = ?1 WHERE id = 1",
+                    [previous_account_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+        }
     }
+    Ok(())
+}
 
-    // Force remove anything that might still exist (including broken junctions)
-    // This is safe because we've already moved any real data
-    let output = std::process::Command::new("cmd")
-        .args(["/C", "rmdir", &riot_data_path.to_string_lossy()])
-        .creation_flags(0x08000000)
-        .output();
+/// Point the Riot data junction at the account's folder and return that folder.
+fn perform_account_switch(account_id: Option<i64>) -> Result<PathBuf, String> {
+    // The steps below move data and rebuild the junction; never let two interleave
+    let guard = SwitchGuard::acquire()?;
+    switch_with_guard(&guard, account_id)
+}
 
-    if let Ok(out) = output {
-        if out.status.success() {
-            log::info!("Successfully cleaned up path with rmdir");
-        }
+/// `perform_account_switch` for callers that already hold the switch guard.
+fn switch_with_guard(_guard: &SwitchGuard, account_id: Option<i64>) -> Result<PathBuf, String> {
+    let settings = get_settings()?;
+    let (target, steps) = plan_account_switch(&settings, account_id)?;
+
+    match account_id {
+        Some(id) => log::info!("Switching to account {} ({})", id, target.display()),
+        None => log::info!("Switching to unselected state"),
     }
 
-    log::info!("Creating junction: {} -> {}", riot_data_path.display(), target.display());
-    fs::create_junction(&riot_data_path, &target)?;
+    let drive = detachable_drive_status(&target);
 
-    let conn = db::init::get_connection(None)?;
-    conn.execute(
-        "UPDATE settings SET active_account_id = ?1 WHERE id = 1",
-        [account_id],
-    )
-    .map_err(|e| e.to_string())?;
+    for step in &steps {
+        log::debug!("Switch step: {:?}", step);
+        run_switch_step(step)?;
+    }
 
-    Ok(())
+    if let Some(warning) = drive {
+        warn_detachable_drive(warning);
+    }
+
+    Ok(target)
+}
+
+/// List what switching to `account_id` would do, without changing anything.
+///
+/// `None` previews a switch to `_unselected`. Steps are in execution order with
+/// resolved paths; the filesystem and database are left untouched.
+#[tauri::command]
+fn preview_account_switch(account_id: Option<i64>) -> Result<Vec<SwitchStep>, String> {
+    let settings = get_settings()?;
+    let (_, steps) = plan_account_switch(&settings, account_id)?;
+    Ok(steps)
 }
 
 fn set_clipboard_text(text: &str) -> Result<(), String> {
@@ -249,16 +418,277 @@ fn set_clipboard_text(text: &str) -> Result<(), String> {
 
 #[tauri::command]
 fn copy_account_password(account_id: i64) -> Result<(), String> {
-    let account = get_account(account_id)?;
+    let password = decrypt_account_password(&get_account(account_id)?)?;
+    set_clipboard_text(&password)
+}
+
+/// Return an account's decrypted password so the UI can show it in a reveal field.
+///
+/// Decrypts exactly like `copy_account_password`; the password is never logged.
+#[tauri::command]
+fn reveal_account_password(account_id: i64) -> Result<String, String> {
+    decrypt_account_password(&get_account(account_id)?)
+}
+
+/// Decrypt the account's stored password with the configured encryption mode,
+/// the same one `create_account` and `update_account` encrypt with.
+fn decrypt_account_password(account: &db::models::Account) -> Result<String, String> {
+    let mode = db::settings::get_encryption_mode()?;
+    crypto::unprotect(stored_password(account)?, mode)
+}
+
+/// The account's encrypted password, or an error when none is stored.
+fn stored_password(account: &db::models::Account) -> Result<&[u8], String> {
     if account.encrypted_password.is_empty() {
         return Err("No password stored".to_string());
     }
-    let password = crypto::dpapi::unprotect_password(&account.encrypted_password)?;
-    set_clipboard_text(&password)
+    Ok(&account.encrypted_password)
+}
+
+/// Remove an account's saved password, e.g. when switching it to cookie-only login.
+#[tauri::command]
+fn clear_account_password(account_id: i64) -> Result<db::models::Account, String> {
+    db::accounts::clear_account_password(account_id)
+}
+
+/// Switch password storage to a key derived from `password`, re-encrypting every account.
+///
+/// Also used to change an existing master password, which requires being unlocked.
+#[tauri::command]
+fn set_master_password(password: String) -> Result<(), String> {
+    if password.is_empty() {
+        return Err("Master password must not be empty".to_string());
+    }
+
+    let current = db::settings::get_encryption_mode()?;
+    if current == crypto::EncryptionMode::MasterPassword && !crypto::master::is_unlocked() {
+        return Err("Unlock with the current master password first".to_string());
+    }
+
+    let salt = crypto::master::generate_salt();
+    let key = crypto::master::derive_key(&password, &salt);
+    let check = crypto::master::make_check(&key)?;
+
+    db::accounts::reencrypt_all_passwords(
+        crypto::EncryptionMode::MasterPassword,
+        Some((&salt, &check)),
+        |encrypted| {
+            let plain = crypto::unprotect(encrypted, current)?;
+            crypto::keyring::encrypt_password(&plain, &key)
+        },
+    )?;
+
+    crypto::master::set_key(Some(key));
+    log::info!("Master password set");
+    Ok(())
+}
+
+/// Go back to machine (DPAPI) encryption. Requires the session to be unlocked.
+#[tauri::command]
+fn clear_master_password() -> Result<(), String> {
+    if db::settings::get_encryption_mode()? != crypto::EncryptionMode::MasterPassword {
+        return Ok(());
+    }
+
+    let key = crypto::master::unlocked_key()?;
+    db::accounts::reencrypt_all_passwords(crypto::EncryptionMode::Machine, None, |encrypted| {
+        let plain = crypto::keyring::decrypt_password(encrypted, &key)?;
+        crypto::dpapi::protect_password(&plain)
+    })?;
+
+    crypto::master::set_key(None);
+    log::info!("Master password cleared, using machine encryption");
+    Ok(())
+}
+
+/// Forget the master password key until the next `unlock`.
+#[tauri::command]
+fn lock() {
+    crypto::master::lock();
+    log::info!("Passwords locked");
+}
+
+/// Whether stored passwords currently need the master password to be used.
+///
+/// Always `false` in machine encryption mode.
+#[tauri::command]
+fn is_locked() -> Result<bool, String> {
+    let mode = db::settings::get_encryption_mode()?;
+    Ok(mode == crypto::EncryptionMode::MasterPassword && !crypto::master::is_unlocked())
+}
+
+/// Unlock stored passwords for this session with the master password.
+#[tauri::command]
+fn unlock(password: String) -> Result<(), String> {
+    let (salt, check) = db::settings::get_master_password_params()?
+        .ok_or("No master password is set")?;
+    crypto::master::unlock(&password, &salt, &check)
 }
 
 #[tauri::command]
 fn get_account_cookies(account_id: i64) -> Result<Option<shop::RiotCookies>, String> {
+    let doc = match read_account_yaml(account_id)? {
+        Some(doc) => doc,
+        None => return Ok(None),
+    };
+
+    let cookies = cookies_from_yaml(&doc);
+
+    if cookies.ssid.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(cookies))
+}
+
+/// When an account's stored ssid session stops working.
+#[derive(serde::Serialize)]
+struct CookieExpiry {
+    /// Unix seconds from the ssid's `exp` claim.
+    expires_at: i64,
+    /// Seconds until expiry; negative once expired.
+    remaining_secs: i64,
+    expired: bool,
+}
+
+/// Read the expiry of an account's ssid cookie.
+///
+/// Returns `None` when the account has no stored session. Errors when the ssid
+/// exists but carries no readable `exp` claim.
+#[tauri::command]
+fn get_cookie_expiry(account_id: i64) -> Result<Option<CookieExpiry>, String> {
+    let ssid = match get_account_cookies(account_id)?.and_then(|c| c.ssid) {
+        Some(ssid) => ssid,
+        None => return Ok(None),
+    };
+
+    cookie_expiry(&ssid)
+        .map(Some)
+        .ok_or_else(|| format!("ssid for account {} has no readable expiry", account_id))
+}
+
+fn cookie_expiry(ssid: &str) -> Option<CookieExpiry> {
+    let expires_at = shop::ssid_expiry(ssid)?;
+    let remaining_secs = expires_at - chrono::Utc::now().timestamp();
+    Some(CookieExpiry {
+        expires_at,
+        remaining_secs,
+        expired: remaining_secs <= 0,
+    })
+}
+
+/// Session health of one account, as reported by `audit_cookie_expiry`.
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum CookieExpiryStatus {
+    Valid { expires_at: i64, remaining_secs: i64 },
+    Expired { expires_at: i64 },
+    /// No settings file or no ssid cookie.
+    NoCookies,
+    /// The settings file or the ssid token could not be read.
+    Unknown,
+}
+
+#[derive(serde::Serialize)]
+struct AccountCookieExpiry {
+    account_id: i64,
+    #[serde(flatten)]
+    status: CookieExpiryStatus,
+}
+
+/// Report the ssid expiry of every account from its stored cookies, without any network call.
+#[tauri::command]
+fn audit_cookie_expiry() -> Result<Vec<AccountCookieExpiry>, String> {
+    let accounts = get_all_accounts()?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|account| {
+            let status = match get_account_cookies(account.id) {
+                Ok(Some(cookies)) => match cookies.ssid.as_deref().and_then(cookie_expiry) {
+                    Some(e) if e.expired => CookieExpiryStatus::Expired {
+                        expires_at: e.expires_at,
+                    },
+                    Some(e) => CookieExpiryStatus::Valid {
+                        expires_at: e.expires_at,
+                        remaining_secs: e.remaining_secs,
+                    },
+                    None => CookieExpiryStatus::Unknown,
+                },
+                Ok(None) => CookieExpiryStatus::NoCookies,
+                Err(e) => {
+                    log::warn!("audit_cookie_expiry: account {}: {}", account.id, e);
+                    CookieExpiryStatus::Unknown
+                }
+            };
+            AccountCookieExpiry {
+                account_id: account.id,
+                status,
+            }
+        })
+        .collect())
+}
+
+/// Why an account cannot fetch its shop without a manual login.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LoginNeededReason {
+    /// No RiotGamesPrivateSettings.yaml in the account's data folder.
+    NoSettingsFile,
+    /// The yaml exists but has no `ssid` cookie.
+    NoSession,
+    /// The yaml could not be read or parsed.
+    Unreadable,
+    /// The account's data folder could not be located, e.g. none is assigned.
+    NoDataFolder,
+}
+
+/// An account that needs one manual login before its shop can be fetched.
+#[derive(serde::Serialize)]
+struct AccountNeedingLogin {
+    account_id: i64,
+    riot_id: String,
+    tagline: String,
+    reason: LoginNeededReason,
+}
+
+/// List accounts whose stored session cannot be used to fetch a shop.
+#[tauri::command]
+fn list_accounts_needing_login() -> Result<Vec<AccountNeedingLogin>, String> {
+    let mut needing_login = Vec::new();
+
+    for account in get_all_accounts()? {
+        let reason = match resolve_account_yaml_path(account.id) {
+            Ok(None) => Some(LoginNeededReason::NoSettingsFile),
+            Ok(Some(_)) => match get_account_cookies(account.id) {
+                Ok(Some(_)) => None,
+                Ok(None) => Some(LoginNeededReason::NoSession),
+                Err(e) => {
+                    log::warn!("Failed to read cookies for account {}: {}", account.id, e);
+                    Some(LoginNeededReason::Unreadable)
+                }
+            },
+            Err(e) => {
+                log::warn!("Failed to resolve yaml for account {}: {}", account.id, e);
+                Some(LoginNeededReason::NoDataFolder)
+            }
+        };
+
+        if let Some(reason) = reason {
+            needing_login.push(AccountNeedingLogin {
+                account_id: account.id,
+                riot_id: account.riot_id,
+                tagline: account.tagline,
+                reason,
+            });
+        }
+    }
+
+    Ok(needing_login)
+}
+
+/// Read and parse an account's RiotGamesPrivateSettings.yaml, if it exists.
+fn read_account_yaml(account_id: i64) -> Result<Option<serde_yaml::Value>, String> {
     let yaml_path = match resolve_account_yaml_path(account_id)? {
         Some(path) => path,
         None => return Ok(None),
@@ -267,9 +697,11 @@ fn get_account_cookies(account_id: i64) -> Result<Option<shop::RiotCookies>, Str
     let content = std::fs::read_to_string(&yaml_path)
         .map_err(|e| format!("Failed to read settings file: {}", e))?;
 
-    let doc: serde_yaml::Value = serde_yaml::from_str(&content)
-        .map_err(|e| format!("Failed to parse YAML: {}", e))?;
+    parse_yaml_document(&content).map(Some)
+}
 
+/// Extract the Riot auth cookies from a parsed RiotGamesPrivateSettings.yaml.
+fn cookies_from_yaml(doc: &serde_yaml::Value) -> shop::RiotCookies {
     let session_cookies = doc
         .get("riot-login")
         .and_then(|v| v.get("persist"))
@@ -312,319 +744,2622 @@ fn get_account_cookies(account_id: i64) -> Result<Option<shop::RiotCookies>, Str
         .and_then(|v| v.as_str())
         .map(|v| v.to_string());
 
-    if cookies.ssid.is_none() {
-        return Ok(None);
-    }
+    cookies
+}
 
-    Ok(Some(cookies))
+/// Read the account's PUUID from the `sub` cookie in its stored yaml.
+///
+/// No network auth is performed. Returns `None` when the yaml is absent or
+/// has no `sub` cookie.
+#[tauri::command]
+fn get_account_puuid(account_id: i64) -> Result<Option<String>, String> {
+    let doc = match read_account_yaml(account_id)? {
+        Some(doc) => doc,
+        None => return Ok(None),
+    };
+
+    Ok(cookies_from_yaml(&doc).sub.filter(|s| !s.is_empty()))
 }
 
-/// Resolve the path to an account's RiotGamesPrivateSettings.yaml.
-fn resolve_account_yaml_path(account_id: i64) -> Result<Option<PathBuf>, String> {
-    let account = get_account(account_id)?;
-    let data_folder = account
-        .data_folder
-        .ok_or("Account has no data directory assigned")?;
+const UTF8_BOM: &str = "\u{feff}";
 
-    let settings = get_settings()?;
-    let account_data_path = match settings.account_data_path {
-        Some(path) => PathBuf::from(path),
-        None => db::init::get_default_account_data_path()?,
+/// Parse the YAML document, ignoring a leading UTF-8 BOM.
+fn parse_yaml_document(content: &str) -> Result<serde_yaml::Value, String> {
+    let body = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    serde_yaml::from_str(body).map_err(|e| format!("Failed to parse YAML: {}", e))
+}
+
+/// Detect an account's region from the `clid` cookie in its stored yaml.
+///
+/// When `persist` is set, the detected region is saved to the account's
+/// `region` column. Returns `None` when the account has no usable cookies.
+#[tauri::command]
+fn detect_account_region(account_id: i64, persist: bool) -> Result<Option<String>, String> {
+    let cookies = match get_account_cookies(account_id)? {
+        Some(c) => c,
+        None => return Ok(None),
     };
 
-    let yaml_path = account_data_path
-        .join(&data_folder)
-        .join("RiotGamesPrivateSettings.yaml");
+    let region = match cookies.clid.as_deref().map(shop::region_from_clid) {
+        Some(r) if !r.is_empty() => r.to_string(),
+        _ => return Ok(None),
+    };
 
-    if yaml_path.exists() {
-        Ok(Some(yaml_path))
-    } else {
-        Ok(None)
+    log::info!("Detected region {} for account {}", region, account_id);
+
+    if persist {
+        db::accounts::set_account_region(account_id, Some(&region))?;
     }
+
+    Ok(Some(region))
 }
 
-/// Update cookie values in the YAML content string without altering formatting.
+/// Set or clear (`None`) the region used for an account's shop requests.
 ///
-/// For session cookies under `riot-login.persist.session.cookies`, this finds
-/// each `- name: <cookie_name>` block and replaces the `value:` line.
-/// For `tdid`, it finds `rso-authenticator` > `tdid` > `value:` and replaces it.
-fn update_yaml_cookie_values(content: &str, cookies: &shop::RiotCookies) -> String {
-    log::debug!("update_yaml_cookie_values: starting YAML cookie replacement");
-    let cookie_updates: &[(&str, &Option<String>)] = &[
-        ("ssid", &cookies.ssid),
-        ("asid", &cookies.asid),
-        ("csid", &cookies.csid),
-        ("ccid", &cookies.ccid),
-        ("clid", &cookies.clid),
-        ("sub", &cookies.sub),
-    ];
+/// When unset, the shard is derived from the account's `clid` cookie.
+#[tauri::command]
+fn set_account_region(
+    account_id: i64,
+    region: Option<String>,
+) -> Result<db::models::Account, String> {
+    let region = normalize_region(region)?;
+    db::accounts::set_account_region(account_id, region.as_deref())
+}
 
-    let mut result = content.to_string();
+/// Rename an account's data folder under `account_data_path`.
+///
+/// The directory and its marker are renamed before `data_folder` is updated.
+/// When the account is active, the junction is rebuilt to point at the new folder,
+/// so Riot Client and Valorant must not be running.
+#[tauri::command]
+fn rename_account_folder(account_id: i64, new_name: String) -> Result<db::models::Account, String> {
+    let new_name = new_name.trim();
+    if !fs::is_valid_folder_name(new_name) {
+        return Err(format!("'{}' is not a valid folder name", new_name));
+    }
+    if new_name.eq_ignore_ascii_case("_unselected") {
+        return Err("'_unselected' is reserved".to_string());
+    }
 
-    for &(cookie_name, cookie_value) in cookie_updates {
-        if let Some(new_val) = cookie_value {
-            // Actual YAML structure:
-            //     -   domain: "auth.riotgames.com"
-            //         hostOnly: true
-            //         ...
-            //         name: "ssid"
-            //         ...
-            //         value: "old_value"
-            // Match `name: "cookie_name"`, skip intermediate fields, then
-            // capture up to and including `value: ` and replace the quoted value.
-            let pattern = format!(
-                r#"(?m)(name:\s*"?{}"?\s*\n(?:\s+\w+:.*\n)*?\s+value:\s*)"[^"]*""#,
-                regex::escape(cookie_name)
-            );
-            if let Ok(re) = regex::Regex::new(&pattern) {
-                let had_match = re.is_match(&result);
-                let replacement = new_val.clone();
-                result = re
-                    .replace(&result, |caps: &regex::Captures| {
-                        format!("{}\"{}\"", &caps[1], replacement)
-                    })
-                    .to_string();
-                if had_match {
-                    log::debug!(
-                        "update_yaml_cookie_values: replaced {} ({} chars)",
-                        cookie_name,
-                        new_val.len()
-                    );
-                } else {
-                    log::debug!(
-                        "update_yaml_cookie_values: no match for {} in YAML",
-                        cookie_name
-                    );
+    // Held across the rename and the junction rebuild so no switch runs in between
+    let guard = SwitchGuard::acquire()?;
+
+    let account = get_account(account_id)?;
+    if account.data_path_override.is_some() {
+        return Err("Account uses a custom data path; clear it before renaming".to_string());
+    }
+    let old_name = account
+        .data_folder
+        .clone()
+        .ok_or("Account has no data folder assigned")?;
+    if old_name == new_name {
+        return Ok(account);
+    }
+
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+    let from = account_data_path.join(&old_name);
+    let to = account_data_path.join(new_name);
+
+    // A case-only rename keeps the same directory on Windows, so it is no collision
+    let case_only = old_name.eq_ignore_ascii_case(new_name);
+    let taken = get_all_accounts()?.iter().any(|a| {
+        a.id != account_id
+            && a.data_folder
+                .as_deref()
+                .is_some_and(|f| f.eq_ignore_ascii_case(new_name))
+    });
+    if taken || (!case_only && to.exists()) {
+        return Err(format!("A folder named '{}' already exists", new_name));
+    }
+
+    let is_active = settings.active_account_id == Some(account_id);
+    if is_active && (process::check_riot_client_running() || process::check_valorant_running()) {
+        return Err("Cannot rename the active account's folder while Riot Client or Valorant is running".to_string());
+    }
+
+    if from.exists() {
+        fs::rename_dir_with_marker(&from, &to)?;
+    } else {
+        log::warn!("rename_account_folder: {} does not exist, updating the record only", from.display());
+    }
+
+    let updated = match db::accounts::set_data_folder(account_id, new_name) {
+        Ok(account) => account,
+        Err(e) => {
+            if to.exists() {
+                if let Err(rollback) = fs::rename_dir_with_marker(&to, &from) {
+                    log::error!("rename_account_folder: failed to roll back rename: {}", rollback);
                 }
             }
-        } else {
-            log::debug!(
-                "update_yaml_cookie_values: skipping {} (no updated value)",
-                cookie_name
-            );
+            return Err(e);
         }
+    };
+    log::info!("Renamed data folder of account {}: {} -> {}", account_id, old_name, new_name);
+
+    if is_active {
+        switch_with_guard(&guard, Some(account_id))?;
     }
 
-    if let Some(new_tdid) = &cookies.tdid {
-        // Actual YAML structure:
-        //   rso-authenticator:
-        //       tdid:
-        //           domain: "riotgames.com"
-        //           ...
-        //           value: "old_value"
-        let pattern =
-            r#"(?m)(rso-authenticator:\s*\n\s+tdid:\s*\n(?:\s+\w+:.*\n)*?\s+value:\s*)"[^"]*""#;
-        if let Ok(re) = regex::Regex::new(pattern) {
-            let had_match = re.is_match(&result);
-            let replacement = new_tdid.clone();
-            result = re
-                .replace(&result, |caps: &regex::Captures| {
-                    format!("{}\"{}\"", &caps[1], replacement)
-                })
-                .to_string();
-            if had_match {
-                log::debug!(
-                    "update_yaml_cookie_values: replaced tdid ({} chars)",
-                    new_tdid.len()
-                );
+    Ok(updated)
+}
+
+/// Add a skin (by skin UUID) to an account's wishlist.
+#[tauri::command]
+fn add_wishlist_item(account_id: i64, skin_uuid: String) -> Result<(), String> {
+    db::add_wishlist_item(account_id, &skin_uuid)
+}
+
+/// Remove a skin from an account's wishlist.
+#[tauri::command]
+fn remove_wishlist_item(account_id: i64, skin_uuid: String) -> Result<(), String> {
+    db::remove_wishlist_item(account_id, &skin_uuid)
+}
+
+/// The skin UUIDs on an account's wishlist.
+#[tauri::command]
+fn get_wishlist(account_id: i64) -> Result<Vec<String>, String> {
+    db::get_wishlist(account_id)
+}
+
+/// Rearrange the account list; returns the accounts in their new order.
+///
+/// Unknown ids are skipped rather than failing the whole reorder.
+#[tauri::command]
+fn reorder_accounts(ordered_ids: Vec<i64>) -> Result<Vec<db::models::Account>, String> {
+    db::accounts::reorder_accounts(&ordered_ids)?;
+    get_all_accounts()
+}
+
+/// Pin or unpin an account at the top of the account list.
+#[tauri::command]
+fn toggle_favorite_account(account_id: i64) -> Result<db::models::Account, String> {
+    db::accounts::toggle_favorite(account_id)
+}
+
+/// State of the Riot Client data junction.
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum JunctionStatus {
+    /// The junction points at an existing folder owned by an account, or at
+    /// `_unselected` when `account_id` is `None`.
+    Valid { target: String, account_id: Option<i64> },
+    /// The junction target no longer exists.
+    Broken { target: String },
+    /// The target exists but belongs to no account.
+    Orphaned { target: String },
+    /// The Riot data path is missing or is a plain directory.
+    NotJunction,
+}
+
+/// Compare Windows paths case-insensitively, ignoring trailing separators.
+fn same_path(a: &std::path::Path, b: &std::path::Path) -> bool {
+    let normalize = |p: &std::path::Path| {
+        p.to_string_lossy()
+            .trim_end_matches(['\\', '/'])
+            .replace('/', "\\")
+            .to_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Check that the Riot data junction points at an existing, known data folder.
+#[tauri::command]
+fn check_junction_validity() -> Result<JunctionStatus, String> {
+    let settings = get_settings()?;
+    let riot_data_path = riot_data_root(&settings)?;
+    let account_data_path = account_data_root(&settings)?;
+
+    if !fs::is_symlink(&riot_data_path)? {
+        return Ok(JunctionStatus::NotJunction);
+    }
+
+    let target = fs::get_junction_target(&riot_data_path)?;
+    let target_str = target.to_string_lossy().to_string();
+
+    if !target.is_dir() {
+        log::warn!("Junction target is missing: {}", target.display());
+        return Ok(JunctionStatus::Broken { target: target_str });
+    }
+
+    if same_path(&target, &account_data_path.join("_unselected")) {
+        return Ok(JunctionStatus::Valid { target: target_str, account_id: None });
+    }
+
+    let owner = get_all_accounts()?.into_iter().find(|account| {
+        db::accounts::resolve_account_data_dir(account, &account_data_path)
+            .is_some_and(|dir| same_path(&dir, &target))
+    });
+
+    Ok(match owner {
+        Some(account) => JunctionStatus::Valid {
+            target: target_str,
+            account_id: Some(account.id),
+        },
+        None => JunctionStatus::Orphaned { target: target_str },
+    })
+}
+
+/// Outcome of `repair_active_junction`.
+#[derive(serde::Serialize)]
+struct JunctionRepair {
+    /// Active account the junction should point at; `None` means `_unselected`.
+    account_id: Option<i64>,
+    expected_target: Option<String>,
+    /// Where the Riot data path pointed; `None` when it was not a junction.
+    found_target: Option<String>,
+    /// Why the junction was rebuilt; `None` when it was already correct.
+    problem: Option<String>,
+    repaired: bool,
+}
+
+/// Make sure the Riot data junction points at the active account's folder.
+///
+/// A plain directory, a dangling junction or one pointing elsewhere is rebuilt
+/// by switching to the active account again, so Riot does not start a fresh,
+/// logged-out profile.
+#[tauri::command]
+fn repair_active_junction() -> Result<JunctionRepair, String> {
+    let settings = get_settings()?;
+    let riot_data_path = riot_data_root(&settings)?;
+    let account_data_path = account_data_root(&settings)?;
+    let account_id = settings.active_account_id;
+
+    let expected = match account_id {
+        Some(id) => db::accounts::resolve_account_data_dir(&get_account(id)?, &account_data_path),
+        None => Some(account_data_path.join("_unselected")),
+    };
+
+    let found = if fs::is_symlink(&riot_data_path)? {
+        Some(fs::get_junction_target(&riot_data_path)?)
+    } else {
+        None
+    };
+
+    let problem = match (&found, &expected) {
+        (None, _) if riot_data_path.is_dir() => Some("Riot data path is a plain directory"),
+        (None, _) => Some("Riot data path is missing"),
+        (Some(target), _) if !target.is_dir() => Some("Junction target no longer exists"),
+        (Some(_), None) => Some("Active account has no data folder assigned"),
+        (Some(target), Some(expected)) if !same_path(target, expected) => {
+            Some("Junction points at a different folder")
+        }
+        _ => None,
+    };
+
+    let mut repair = JunctionRepair {
+        account_id,
+        expected_target: expected.map(|p| p.to_string_lossy().to_string()),
+        found_target: found.map(|p| p.to_string_lossy().to_string()),
+        problem: problem.map(str::to_string),
+        repaired: false,
+    };
+
+    if let Some(problem) = problem {
+        log::warn!("repair_active_junction: {}, rebuilding for {:?}", problem, account_id);
+        let target = switch_account_checked(account_id)?;
+        repair.expected_target = Some(target.to_string_lossy().to_string());
+        repair.repaired = true;
+    }
+
+    Ok(repair)
+}
+
+/// Everything an account switch depends on, gathered in one call for bug reports.
+#[derive(serde::Serialize)]
+struct SwitchReadiness {
+    junction_works: bool,
+    junction_error: Option<String>,
+    symlink_privilege: fs::SymlinkPrivilegeStatus,
+    cross_volume: bool,
+    riot_client_running: bool,
+    vanguard_running: bool,
+}
+
+/// Report whether an account switch can succeed right now.
+///
+/// The junction probe runs next to the Riot data directory, where the real
+/// junction is created.
+#[tauri::command]
+fn diagnose_switch_readiness() -> Result<SwitchReadiness, String> {
+    let settings = get_settings()?;
+    let riot_data_path = riot_data_root(&settings)?;
+    let account_data_path = account_data_root(&settings)?;
+
+    let junction_result = match riot_data_path.parent() {
+        Some(parent) => fs::test_junction_capability(parent),
+        None => Err(format!("{} has no parent directory", riot_data_path.display())),
+    };
+    if let Err(ref e) = junction_result {
+        log::warn!("Junction capability test failed: {}", e);
+    }
+
+    Ok(SwitchReadiness {
+        junction_works: junction_result.is_ok(),
+        junction_error: junction_result.err(),
+        symlink_privilege: fs::check_symlink_privilege(),
+        cross_volume: !fs::on_same_volume(&riot_data_path, &account_data_path),
+        riot_client_running: process::check_riot_client_running(),
+        vanguard_running: process::check_vanguard_running(),
+    })
+}
+
+/// Riot Client auth state inferred from the live data folder.
+#[derive(serde::Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum RiotLoginState {
+    NotRunning,
+    /// Riot is running but the live yaml holds no session cookie.
+    LoginScreen { account_id: Option<i64> },
+    /// Riot is running with a persisted session for `puuid`.
+    LoggedIn {
+        account_id: Option<i64>,
+        puuid: Option<String>,
+    },
+}
+
+/// Infer whether the running Riot Client is signed in, and as which account.
+///
+/// Reads the yaml behind the junction (the active account's folder or
+/// `_unselected`). A session that was not kept with "Stay signed in" is never
+/// persisted there, so it is reported as the login screen.
+#[tauri::command]
+fn get_riot_login_state() -> Result<RiotLoginState, String> {
+    if !process::check_riot_client_running() {
+        return Ok(RiotLoginState::NotRunning);
+    }
+
+    let settings = get_settings()?;
+    let riot_data_path = riot_data_root(&settings)?;
+
+    let account_id = match check_junction_validity()? {
+        JunctionStatus::Valid { account_id, .. } => account_id,
+        _ => None,
+    };
+
+    let cookies = match find_yaml_in_data_dir(&riot_data_path) {
+        Some(path) => {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read settings file: {}", e))?;
+            Some(cookies_from_yaml(&parse_yaml_document(&content)?))
+        }
+        None => None,
+    };
+
+    Ok(match cookies {
+        Some(cookies) if cookies.ssid.as_deref().is_some_and(|s| !s.is_empty()) => {
+            RiotLoginState::LoggedIn {
+                account_id,
+                puuid: cookies.sub.filter(|s| !s.is_empty()),
+            }
+        }
+        _ => RiotLoginState::LoginScreen { account_id },
+    })
+}
+
+/// Marker check for one account's data folder.
+#[derive(serde::Serialize)]
+struct DataMarkerReport {
+    account_id: i64,
+    data_folder: Option<String>,
+    path: Option<String>,
+    #[serde(flatten)]
+    status: fs::MarkerStatus,
+}
+
+/// Check every account's data folder for the marker named after its `data_folder`.
+///
+/// Only accounts whose marker is not `ok` are returned.
+#[tauri::command]
+fn audit_data_markers() -> Result<Vec<DataMarkerReport>, String> {
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+    let mut reports = Vec::new();
+
+    for account in get_all_accounts()? {
+        let (Some(folder), Some(dir)) = (
+            account.data_folder.clone(),
+            db::accounts::resolve_account_data_dir(&account, &account_data_path),
+        ) else {
+            reports.push(DataMarkerReport {
+                account_id: account.id,
+                data_folder: None,
+                path: None,
+                status: fs::MarkerStatus::FolderMissing,
+            });
+            continue;
+        };
+
+        let status = fs::check_dir_marker(&dir, &folder)?;
+        if status != fs::MarkerStatus::Ok {
+            log::warn!("Marker audit: account {} at {}: {:?}", account.id, dir.display(), status);
+            reports.push(DataMarkerReport {
+                account_id: account.id,
+                data_folder: Some(folder),
+                path: Some(dir.to_string_lossy().to_string()),
+                status,
+            });
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Accounts sharing a `data_folder` with another account, grouped by folder.
+#[tauri::command]
+fn find_duplicate_folders() -> Result<Vec<db::models::Account>, String> {
+    db::accounts::find_duplicate_folders()
+}
+
+/// On-disk size of one data folder. `account_id` is `None` for `_unselected`
+/// and for orphaned folders no account refers to.
+#[derive(serde::Serialize)]
+struct DataFolderSize {
+    account_id: Option<i64>,
+    folder: String,
+    path: String,
+    bytes: u64,
+}
+
+/// Report the size of every account's data folder, `_unselected`, and orphaned folders.
+#[tauri::command]
+fn get_account_data_sizes() -> Result<Vec<DataFolderSize>, String> {
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+    let mut sizes = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for account in get_all_accounts()? {
+        let Some(dir) = db::accounts::resolve_account_data_dir(&account, &account_data_path) else {
+            continue;
+        };
+        let folder = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        seen.insert(dir.clone());
+        sizes.push(DataFolderSize {
+            account_id: Some(account.id),
+            folder,
+            path: dir.to_string_lossy().to_string(),
+            bytes: fs::dir_size(&dir),
+        });
+    }
+
+    if let Ok(entries) = std::fs::read_dir(&account_data_path) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !path.is_dir() || seen.contains(&path) {
+                continue;
+            }
+            sizes.push(DataFolderSize {
+                account_id: None,
+                folder: entry.file_name().to_string_lossy().to_string(),
+                path: path.to_string_lossy().to_string(),
+                bytes: fs::dir_size(&path),
+            });
+        }
+    }
+
+    Ok(sizes)
+}
+
+/// Export the account roster as pretty JSON, optionally writing it to `path`.
+///
+/// Secrets are never included; see `AccountListEntry` for the exported fields.
+#[tauri::command]
+fn export_account_list(path: Option<String>) -> Result<String, String> {
+    let entries: Vec<db::models::AccountListEntry> =
+        get_all_accounts()?.iter().map(Into::into).collect();
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+
+    if let Some(path) = path {
+        std::fs::write(&path, &json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        log::info!("Exported {} accounts to {}", entries.len(), path);
+    }
+
+    Ok(json)
+}
+
+/// Render one credential for a password manager.
+///
+/// `keepass_csv` follows the KeePass CSV import layout with a header row;
+/// `json` is a single object with `riot_id`, `username` and `password`.
+fn format_credential(
+    format: &str,
+    riot_id: &str,
+    username: &str,
+    password: &str,
+) -> Result<String, String> {
+    match format {
+        "keepass_csv" => {
+            let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+            Ok(format!(
+                "\"Account\",\"Login Name\",\"Password\",\"Web Site\",\"Comments\"\n{},{},{},{},{}\n",
+                quote(riot_id),
+                quote(username),
+                quote(password),
+                quote("https://account.riotgames.com"),
+                quote("Exported from ValoAccounts")
+            ))
+        }
+        "json" => serde_json::to_string_pretty(&serde_json::json!({
+            "riot_id": riot_id,
+            "username": username,
+            "password": password,
+        }))
+        .map_err(|e| e.to_string()),
+        other => Err(format!(
+            "Unknown credential format '{}': expected keepass_csv or json",
+            other
+        )),
+    }
+}
+
+/// Export one account's login with its decrypted password as `keepass_csv` or `json`.
+///
+/// The output contains a plaintext secret, so `confirm` must be set and the
+/// result is returned to the caller only, never logged or written to disk here.
+#[tauri::command]
+fn export_account_credential(
+    account_id: i64,
+    format: String,
+    confirm: bool,
+) -> Result<String, String> {
+    if !confirm {
+        return Err("Exporting a plaintext password requires confirmation".to_string());
+    }
+
+    let account = get_account(account_id)?;
+    let username = account
+        .username
+        .as_deref()
+        .ok_or("Account has no username stored")?;
+    let password = decrypt_account_password(&account)?;
+    let riot_id = format!("{}#{}", account.riot_id, account.tagline);
+
+    log::info!("Exporting credential for account {} as {}", account_id, format);
+    format_credential(&format, &riot_id, username, &password)
+}
+
+/// Back up every account to `path`, with passwords re-encrypted under `passphrase`.
+///
+/// The file is portable across machines and Windows installs; data folders are
+/// not included. Returns the number of accounts written.
+#[tauri::command]
+fn export_accounts(path: String, passphrase: String) -> Result<usize, String> {
+    let accounts = get_all_accounts()?
+        .into_iter()
+        .map(|account| {
+            let password = if account.has_password {
+                Some(decrypt_account_password(&account)?)
             } else {
-                log::debug!("update_yaml_cookie_values: no match for tdid in YAML");
+                None
+            };
+            Ok((account, password))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let backup = db::backup::build_backup(&accounts, &passphrase)?;
+    let json = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    log::info!("Exported {} accounts to {}", accounts.len(), path);
+    Ok(accounts.len())
+}
+
+/// Accounts created by `import_accounts` and the Riot IDs it left out.
+#[derive(serde::Serialize)]
+struct ImportResult {
+    created: Vec<db::models::Account>,
+    /// `riot_id#tagline` of backup entries that already exist, or repeat an earlier entry.
+    skipped: Vec<String>,
+}
+
+/// Recreate the accounts in a backup written by `export_accounts`.
+///
+/// Each account gets a new id and a freshly created data folder, as if added
+/// by hand. Entries whose Riot ID already exists are skipped, so importing the
+/// same file twice is harmless. Every entry is validated before anything is
+/// written, and a failure part-way removes the accounts created so far.
+#[tauri::command]
+fn import_accounts(path: String, passphrase: String) -> Result<ImportResult, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let backup = serde_json::from_str(&json).map_err(|e| format!("Invalid backup file: {}", e))?;
+
+    let mut seen: std::collections::HashSet<String> = get_all_accounts()?
+        .iter()
+        .map(|a| format!("{}#{}", a.riot_id, a.tagline).to_lowercase())
+        .collect();
+
+    let mut pending = Vec::new();
+    let mut skipped = Vec::new();
+    for (entry, password) in db::backup::open_backup(backup, &passphrase)? {
+        let name = format!("{}#{}", entry.riot_id, entry.tagline);
+        if let Some(ref args) = entry.launch_args {
+            process::parse_launch_args(args).map_err(|e| format!("{}: {}", name, e))?;
+        }
+        let region = normalize_region(entry.region.clone()).map_err(|e| format!("{}: {}", name, e))?;
+
+        if !seen.insert(name.to_lowercase()) {
+            skipped.push(name);
+            continue;
+        }
+        pending.push((entry, password, region));
+    }
+
+    let mut created = Vec::new();
+    let create_all = || -> Result<(), String> {
+        for (entry, password, region) in pending {
+            created.push(create_account(CreateAccountData {
+                riot_id: entry.riot_id,
+                tagline: entry.tagline,
+                username: entry.username,
+                password,
+                rank: entry.rank,
+                use_current_data: false,
+                launch_args: entry.launch_args,
+            })?);
+            if let Some(region) = region {
+                let account = created.last_mut().expect("just pushed");
+                *account = db::accounts::set_account_region(account.id, Some(&region))?;
+            }
+        }
+        Ok(())
+    };
+    if let Err(e) = create_all() {
+        log::error!("Import failed, removing {} accounts created so far: {}", created.len(), e);
+        for account in &created {
+            discard_imported_account(account);
+        }
+        return Err(e);
+    }
+
+    log::info!(
+        "Imported {} accounts from {} ({} skipped as duplicates)",
+        created.len(),
+        path,
+        skipped.len()
+    );
+    Ok(ImportResult { created, skipped })
+}
+
+/// Undo `create_account` for an account made by a failed import: its folder is new and empty of user data.
+fn discard_imported_account(account: &db::models::Account) {
+    if let Ok(root) = get_settings().and_then(|s| account_data_root(&s)) {
+        if let Some(dir) = db::accounts::resolve_account_data_dir(account, &root) {
+            if let Err(e) = std::fs::remove_dir_all(&dir) {
+                log::warn!("Failed to remove {}: {}", dir.display(), e);
+            }
+        }
+    }
+    if let Err(e) = db::accounts::delete_account(account.id) {
+        log::warn!("Failed to remove imported account {}: {}", account.id, e);
+    }
+}
+
+/// Skin offers seen by an account, once per rotation and newest first, derived
+/// from the storefront history; `skin_uuid` narrows it to one skin.
+#[tauri::command]
+fn get_shop_history(
+    account_id: i64,
+    skin_uuid: Option<String>,
+) -> Result<Vec<shop::ShopHistoryRow>, String> {
+    shop::load_shop_history(account_id, skin_uuid.as_deref())
+}
+
+/// Write every recorded storefront to `path` as CSV, one row per daily or night market offer.
+///
+/// Skins missing from the skins database are written as their UUID. Returns
+/// the number of recorded fetches exported.
+#[tauri::command]
+fn export_shop_history_csv(path: String) -> Result<usize, String> {
+    let entries = shop::load_storefront_history()?;
+
+    let account_names: std::collections::HashMap<i64, String> = get_all_accounts()?
+        .into_iter()
+        .map(|a| (a.id, format!("{}#{}", a.riot_id, a.tagline)))
+        .collect();
+
+    let mut uuids: Vec<String> = entries
+        .iter()
+        .flat_map(|e| {
+            let daily = e.storefront.daily_offers.iter().map(|o| o.skin_uuid.clone());
+            let night = e.storefront.night_market.iter().flatten().map(|o| o.skin_uuid.clone());
+            daily.chain(night)
+        })
+        .collect();
+    uuids.sort();
+    uuids.dedup();
+
+    let skin_names: std::collections::HashMap<String, String> = item_displays(uuids)?
+        .into_iter()
+        .filter_map(|(uuid, display)| Some((uuid, display.name?)))
+        .collect();
+
+    let csv = shop::history_to_csv(&entries, &account_names, &skin_names);
+    std::fs::write(&path, csv).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    log::info!("Exported {} storefront history entries to {}", entries.len(), path);
+
+    Ok(entries.len())
+}
+
+/// Group accounts that share a PUUID, so duplicates can be merged or archived.
+///
+/// Accounts without a readable yaml or `sub` cookie are skipped.
+#[tauri::command]
+fn find_duplicate_accounts() -> Result<Vec<db::models::DuplicateAccountGroup>, String> {
+    let mut by_puuid: std::collections::BTreeMap<String, Vec<db::models::Account>> =
+        std::collections::BTreeMap::new();
+
+    for account in get_all_accounts()? {
+        match get_account_puuid(account.id) {
+            Ok(Some(puuid)) => by_puuid.entry(puuid).or_default().push(account),
+            Ok(None) => {}
+            Err(e) => log::warn!("Skipping account {} in duplicate scan: {}", account.id, e),
+        }
+    }
+
+    Ok(by_puuid
+        .into_iter()
+        .filter(|(_, accounts)| accounts.len() > 1)
+        .map(|(puuid, accounts)| db::models::DuplicateAccountGroup { puuid, accounts })
+        .collect())
+}
+
+/// Resolve the path to an account's RiotGamesPrivateSettings.yaml.
+fn resolve_account_yaml_path(account_id: i64) -> Result<Option<PathBuf>, String> {
+    let account = get_account(account_id)?;
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+
+    let data_dir = db::accounts::resolve_account_data_dir(&account, &account_data_path)
+        .ok_or("Account has no data directory assigned")?;
+
+    Ok(find_yaml_in_data_dir(&data_dir))
+}
+
+/// Locations of RiotGamesPrivateSettings.yaml relative to a data folder, in
+/// lookup order. Some Riot Client versions nest it under `Config`.
+const YAML_CANDIDATES: &[&str] = &[
+    "RiotGamesPrivateSettings.yaml",
+    "Config/RiotGamesPrivateSettings.yaml",
+];
+
+/// Return the first existing RiotGamesPrivateSettings.yaml under `data_dir`.
+fn find_yaml_in_data_dir(data_dir: &std::path::Path) -> Option<PathBuf> {
+    YAML_CANDIDATES
+        .iter()
+        .map(|candidate| data_dir.join(candidate))
+        .find(|path| path.is_file())
+}
+
+/// Temp files and `.bak-<secs>` backups left next to the yaml candidates under `data_dir`.
+fn find_yaml_leftovers(data_dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut leftovers = Vec::new();
+
+    for candidate in YAML_CANDIDATES {
+        let candidate_path = data_dir.join(candidate);
+        let (Some(dir), Some(yaml_name)) = (candidate_path.parent(), candidate_path.file_name())
+        else {
+            continue;
+        };
+        let yaml_name = yaml_name.to_string_lossy();
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(suffix) = name.strip_prefix(yaml_name.as_ref()) else {
+                continue;
+            };
+            if (suffix == ".tmp" || suffix.starts_with(".bak-")) && entry.path().is_file() {
+                leftovers.push(entry.path());
             }
         }
+    }
+
+    leftovers
+}
+
+/// Update cookie values in the YAML content string without altering formatting.
+///
+/// For session cookies under `riot-login.persist.session.cookies`, this finds
+/// each `- name: <cookie_name>` block and replaces the `value:` line.
+/// For `tdid`, it finds `rso-authenticator` > `tdid` > `value:` and replaces it.
+fn update_yaml_cookie_values(content: &str, cookies: &shop::RiotCookies) -> String {
+    log::debug!("update_yaml_cookie_values: starting YAML cookie replacement");
+    let cookie_updates: &[(&str, &Option<String>)] = &[
+        ("ssid", &cookies.ssid),
+        ("asid", &cookies.asid),
+        ("csid", &cookies.csid),
+        ("ccid", &cookies.ccid),
+        ("clid", &cookies.clid),
+        ("sub", &cookies.sub),
+    ];
+
+    let mut result = content.to_string();
+
+    for &(cookie_name, cookie_value) in cookie_updates {
+        if let Some(new_val) = cookie_value {
+            // Actual YAML structure:
+            //     -   domain: "auth.riotgames.com"
+            //         hostOnly: true
+            //         ...
+            //         name: "ssid"
+            //         ...
+            //         value: "old_value"
+            // Match `name: "cookie_name"`, skip intermediate fields, then
+            // capture up to and including `value: ` and replace the quoted value.
+            let pattern = format!(
+                r#"(?m)(name:\s*"?{}"?\s*\n(?:\s+\w+:.*\n)*?\s+value:\s*)"[^"]*""#,
+                regex::escape(cookie_name)
+            );
+            if let Ok(re) = regex::Regex::new(&pattern) {
+                let had_match = re.is_match(&result);
+                let replacement = new_val.clone();
+                result = re
+                    .replace(&result, |caps: &regex::Captures| {
+                        format!("{}\"{}\"", &caps[1], replacement)
+                    })
+                    .to_string();
+                if had_match {
+                    log::debug!(
+                        "update_yaml_cookie_values: replaced {} ({} chars)",
+                        cookie_name,
+                        new_val.len()
+                    );
+                } else {
+                    log::debug!(
+                        "update_yaml_cookie_values: no match for {} in YAML",
+                        cookie_name
+                    );
+                }
+            }
+        } else {
+            log::debug!(
+                "update_yaml_cookie_values: skipping {} (no updated value)",
+                cookie_name
+            );
+        }
+    }
+
+    if let Some(new_tdid) = &cookies.tdid {
+        // Actual YAML structure:
+        //   rso-authenticator:
+        //       tdid:
+        //           domain: "riotgames.com"
+        //           ...
+        //           value: "old_value"
+        let pattern =
+            r#"(?m)(rso-authenticator:\s*\n\s+tdid:\s*\n(?:\s+\w+:.*\n)*?\s+value:\s*)"[^"]*""#;
+        if let Ok(re) = regex::Regex::new(pattern) {
+            let had_match = re.is_match(&result);
+            let replacement = new_tdid.clone();
+            result = re
+                .replace(&result, |caps: &regex::Captures| {
+                    format!("{}\"{}\"", &caps[1], replacement)
+                })
+                .to_string();
+            if had_match {
+                log::debug!(
+                    "update_yaml_cookie_values: replaced tdid ({} chars)",
+                    new_tdid.len()
+                );
+            } else {
+                log::debug!("update_yaml_cookie_values: no match for tdid in YAML");
+            }
+        }
+    } else {
+        log::debug!("update_yaml_cookie_values: skipping tdid (no updated value)");
+    }
+
+    let changed = content != result;
+    log::debug!(
+        "update_yaml_cookie_values: done, content_changed={}",
+        changed
+    );
+
+    result
+}
+
+/// Apply cookie updates to raw file content, preserving its BOM and line endings.
+///
+/// The replacement regexes are written against `\n`, so CRLF content is
+/// normalized for matching and converted back afterwards. Unchanged content is
+/// returned as-is so the caller's no-op check still holds.
+fn update_yaml_file_content(content: &str, cookies: &shop::RiotCookies) -> String {
+    let (bom, body) = match content.strip_prefix(UTF8_BOM) {
+        Some(rest) => (UTF8_BOM, rest),
+        None => ("", content),
+    };
+
+    let crlf = body.contains("\r\n");
+    let normalized = if crlf {
+        body.replace("\r\n", "\n")
+    } else {
+        body.to_string()
+    };
+
+    let updated = update_yaml_cookie_values(&normalized, cookies);
+    if updated == normalized {
+        return content.to_string();
+    }
+
+    let restored = if crlf {
+        updated.replace('\n', "\r\n")
+    } else {
+        updated
+    };
+
+    format!("{}{}", bom, restored)
+}
+
+const RENAME_ATTEMPTS: u32 = 3;
+
+/// Rename `tmp_path` over `target`, retrying on transient sharing violations.
+///
+/// Antivirus scanners or the Riot Client can briefly hold the yaml open. If every
+/// rename fails, `contents` is written in place instead. The temp file never outlives
+/// this call.
+fn replace_with_temp_file(
+    tmp_path: &std::path::Path,
+    target: &std::path::Path,
+    contents: &str,
+) -> Result<(), String> {
+    let mut last_error = None;
+
+    for attempt in 1..=RENAME_ATTEMPTS {
+        match std::fs::rename(tmp_path, target) {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                log::warn!(
+                    "Rename of {} failed (attempt {}/{}): {}",
+                    tmp_path.display(),
+                    attempt,
+                    RENAME_ATTEMPTS,
+                    e
+                );
+                last_error = Some(e);
+                if attempt < RENAME_ATTEMPTS {
+                    std::thread::sleep(std::time::Duration::from_millis(100 * u64::from(attempt)));
+                }
+            }
+        }
+    }
+
+    log::warn!(
+        "Falling back to in-place overwrite of {} after rename failures",
+        target.display()
+    );
+    let result = std::fs::write(target, contents);
+    let _ = std::fs::remove_file(tmp_path);
+
+    result.map_err(|e| {
+        format!(
+            "Failed to replace settings file (rename: {}, overwrite: {})",
+            last_error.map_or_else(String::new, |e| e.to_string()),
+            e
+        )
+    })
+}
+
+/// Whether writing `incoming` would change anything compared to `current`.
+///
+/// `None` fields in `incoming` are never written, so only new or rotated values count.
+fn cookies_need_write(current: &shop::RiotCookies, incoming: &shop::RiotCookies) -> bool {
+    let pairs = [
+        (&current.ssid, &incoming.ssid),
+        (&current.asid, &incoming.asid),
+        (&current.csid, &incoming.csid),
+        (&current.ccid, &incoming.ccid),
+        (&current.clid, &incoming.clid),
+        (&current.sub, &incoming.sub),
+        (&current.tdid, &incoming.tdid),
+    ];
+    pairs
+        .iter()
+        .any(|(current, incoming)| incoming.is_some() && current != incoming)
+}
+
+/// Persist cookies returned by a fetch, skipping the rewrite if none differ from the yaml.
+///
+/// Compares against what is on disk rather than what was sent, since the file
+/// may have changed (e.g. Riot Client rotated the session) during the fetch.
+fn write_back_cookies(account_id: i64, returned: &shop::RiotCookies) {
+    match get_account_cookies(account_id) {
+        Ok(Some(stored)) if !cookies_need_write(&stored, returned) => {
+            log::debug!("Cookies unchanged for account {}, skipping write-back", account_id);
+            return;
+        }
+        Ok(_) => {}
+        Err(e) => log::debug!("Could not read stored cookies for account {}: {}", account_id, e),
+    }
+
+    if let Err(e) = save_account_cookies(account_id, returned) {
+        log::warn!("Failed to save updated cookies for account {}: {}", account_id, e);
+    }
+}
+
+fn save_account_cookies(account_id: i64, cookies: &shop::RiotCookies) -> Result<(), String> {
+    log::debug!("save_account_cookies: starting for account {}", account_id);
+
+    let yaml_path = match resolve_account_yaml_path(account_id)? {
+        Some(path) => {
+            log::debug!("save_account_cookies: resolved YAML path: {}", path.display());
+            path
+        }
+        None => {
+            log::info!(
+                "Skipping cookie save for account {}: YAML file does not exist",
+                account_id
+            );
+            return Ok(());
+        }
+    };
+
+    let content = std::fs::read_to_string(&yaml_path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+    log::debug!(
+        "save_account_cookies: read YAML file ({} bytes)",
+        content.len()
+    );
+
+    let updated_content = update_yaml_file_content(&content, cookies);
+
+    if content == updated_content {
+        log::debug!("save_account_cookies: no changes detected, skipping write");
+        return Ok(());
+    }
+
+    // Atomic write: write to a temp file, then rename over the original
+    let tmp_path = yaml_path.with_extension("yaml.tmp");
+    log::debug!(
+        "save_account_cookies: writing {} bytes to temp file: {}",
+        updated_content.len(),
+        tmp_path.display()
+    );
+    if let Err(e) = std::fs::write(&tmp_path, &updated_content) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(format!("Failed to write temp file: {}", e));
+    }
+
+    log::debug!("save_account_cookies: renaming temp file to YAML path");
+    replace_with_temp_file(&tmp_path, &yaml_path, &updated_content)?;
+
+    log::info!(
+        "save_account_cookies: successfully saved updated cookies for account {}",
+        account_id
+    );
+    Ok(())
+}
+
+/// `get_shop` response: the storefront plus the daily offers on the account's wishlist.
+#[derive(serde::Serialize)]
+struct ShopResponse {
+    #[serde(flatten)]
+    storefront: shop::Storefront,
+    /// `skin_uuid` of each daily offer whose skin is wishlisted.
+    wishlist_hits: Vec<String>,
+}
+
+/// Daily offers whose skin is on the account's wishlist.
+///
+/// Offer UUIDs are skin level UUIDs, so each is resolved to its skin first;
+/// offers missing from skins.db are compared as-is. Lookup errors yield no hits.
+fn wishlist_hits(account_id: i64, offers: &[shop::DailyOffer]) -> Vec<String> {
+    let wishlist = match db::get_wishlist(account_id) {
+        Ok(w) => w,
+        Err(e) => {
+            log::warn!("Failed to load wishlist for account {}: {}", account_id, e);
+            return Vec::new();
+        }
+    };
+    if wishlist.is_empty() {
+        return Vec::new();
+    }
+
+    offers
+        .iter()
+        .filter(|offer| {
+            let skin_uuid = match skins::get_skin_by_level_uuid(&offer.skin_uuid) {
+                Ok(Some(skin)) => skin.uuid,
+                _ => offer.skin_uuid.clone(),
+            };
+            wishlist.contains(&skin_uuid.to_lowercase())
+        })
+        .map(|offer| offer.skin_uuid.clone())
+        .collect()
+}
+
+/// Fetch the daily shop and night market, returning a cached result when valid.
+///
+/// VP costs are zeroed in the response when `show_vp_costs` is off; the cache
+/// always keeps the real prices.
+#[tauri::command]
+async fn get_shop(
+    app: tauri::AppHandle,
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<ShopResponse, String> {
+    let mut storefront = load_or_fetch_storefront(&app, account_id, cookies).await?;
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    let wishlist_hits = wishlist_hits(account_id, &storefront.daily_offers);
+    if !wishlist_hits.is_empty() {
+        log::info!(
+            "get_shop: {} wishlisted skin(s) in account {}'s daily shop",
+            wishlist_hits.len(),
+            account_id
+        );
+    }
+
+    Ok(ShopResponse {
+        storefront,
+        wishlist_hits,
+    })
+}
+
+/// Run the shop pipeline once for an account and report each stage with timings.
+///
+/// The storefront is not cached; rotated cookies are still written back so a
+/// diagnosis never invalidates the stored session.
+#[tauri::command]
+async fn diagnose_shop(account_id: i64) -> Result<shop::ShopDiagnosis, String> {
+    let cookies = get_account_cookies(account_id)?.ok_or("No stored session for this account")?;
+
+    let region = account_region(account_id)?;
+    let (diagnosis, updated_cookies) = shop::diagnose_shop(cookies, region.as_deref()).await;
+    if let Some(updated) = updated_cookies {
+        write_back_cookies(account_id, &updated);
+    }
+
+    match diagnosis.failed_stage {
+        Some(stage) => log::warn!("diagnose_shop: account {} failed at {}", account_id, stage),
+        None => log::info!("diagnose_shop: account {} passed every stage", account_id),
+    }
+    Ok(diagnosis)
+}
+
+/// Like `get_shop`, but always fetches live and replaces the cached storefront.
+///
+/// For when the store rotated before the cached copy expired.
+#[tauri::command]
+async fn refresh_shop(
+    app: tauri::AppHandle,
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<shop::Storefront, String> {
+    log::info!("refresh_shop: bypassing cache for account {}", account_id);
+    let mut storefront = fetch_and_cache_storefront(&app, account_id, cookies).await?;
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// `get_shop` for the active account, using its stored cookies.
+#[tauri::command]
+async fn get_active_shop(app: tauri::AppHandle) -> Result<shop::Storefront, String> {
+    let settings = get_settings()?;
+    let account_id = settings.active_account_id.ok_or("No account is active")?;
+    let cookies = get_account_cookies(account_id)?
+        .ok_or("The active account has no stored session; log in once with the Riot Client")?;
+
+    let mut storefront = load_or_fetch_storefront(&app, account_id, cookies).await?;
+
+    if !settings.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// The account's region, which overrides the shard derived from `clid` on every fetch.
+fn account_region(account_id: i64) -> Result<Option<String>, String> {
+    Ok(get_account(account_id)?.region.filter(|r| !r.is_empty()))
+}
+
+/// Return the cached storefront, or fetch it and write back the cache and cookies.
+///
+/// A fresh fetch also raises `bundle-discount-alert` for deep bundle discounts.
+async fn load_or_fetch_storefront(
+    app: &tauri::AppHandle,
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<shop::Storefront, String> {
+    log::debug!("get_shop: called for account {}", account_id);
+
+    if let Some(cached) = shop::load_cached_storefront(account_id) {
+        log::debug!("get_shop: returning cached storefront for account {}", account_id);
+        return Ok(cached);
+    }
+
+    log::debug!("get_shop: no cache, fetching storefront for account {}", account_id);
+    fetch_and_cache_storefront(app, account_id, cookies).await
+}
+
+/// Fetch the storefront live, overwrite the cache, and write back rotated cookies.
+async fn fetch_and_cache_storefront(
+    app: &tauri::AppHandle,
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<shop::Storefront, String> {
+    let region = account_region(account_id)?;
+    let (storefront, updated_cookies) =
+        shop::fetch_storefront_in_region(cookies, region.as_deref())
+            .await
+            .map_err(|e| e.to_string())?;
+
+    log::debug!("get_shop: storefront fetched, saving cache");
+    shop::save_storefront_cache(account_id, &storefront);
+    shop::record_storefront_history(account_id, &storefront);
+
+    log::debug!("get_shop: persisting updated cookies to YAML");
+    write_back_cookies(account_id, &updated_cookies);
+
+    emit_bundle_discount_alerts(app, account_id, &storefront);
+
+    Ok(storefront)
+}
+
+/// Emit `bundle-discount-alert` for each bundle at or above the configured
+/// discount threshold, once per bundle offer. No-op when the threshold is unset.
+fn emit_bundle_discount_alerts(
+    app: &tauri::AppHandle,
+    account_id: i64,
+    storefront: &shop::Storefront,
+) {
+    use tauri::Emitter;
+
+    let threshold = match get_settings().ok().and_then(|s| s.bundle_discount_alert_threshold) {
+        Some(t) => t,
+        None => return,
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    for alert in shop::bundle_discount_alerts(account_id, storefront, threshold, now) {
+        log::info!(
+            "Bundle discount alert: {} at {:.0}% (account {})",
+            alert.bundle_name,
+            alert.discount_percent,
+            account_id
+        );
+        if let Err(e) = app.emit("bundle-discount-alert", alert) {
+            log::warn!("Failed to emit bundle-discount-alert: {}", e);
+        }
+    }
+}
+
+/// Fetch the storefront live with a pinned client version, bypassing the cache read.
+///
+/// The result is still cached and rotated cookies are written back, as in `get_shop`.
+#[tauri::command]
+async fn get_shop_with_version(
+    app: tauri::AppHandle,
+    account_id: i64,
+    cookies: shop::RiotCookies,
+    client_version: String,
+) -> Result<shop::Storefront, String> {
+    let client_version = client_version.trim().to_string();
+    if client_version.is_empty() {
+        return Err("Client version must not be empty".to_string());
+    }
+
+    log::info!(
+        "get_shop_with_version: fetching account {} with client version {}",
+        account_id,
+        client_version
+    );
+    let region = account_region(account_id)?;
+    let (mut storefront, updated_cookies) =
+        shop::fetch_storefront_with_version(cookies, client_version, region.as_deref())
+            .await
+            .map_err(|e| e.to_string())?;
+
+    shop::save_storefront_cache(account_id, &storefront);
+    shop::record_storefront_history(account_id, &storefront);
+    write_back_cookies(account_id, &updated_cookies);
+
+    emit_bundle_discount_alerts(&app, account_id, &storefront);
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// Pd shards accepted by `get_shop_from_ssid`, plus regions served by another shard.
+const KNOWN_SHARDS: &[&str] = &["ap", "eu", "kr", "na", "br", "latam"];
+
+/// Trim and lowercase a region, treating blank as unset; anything not in `KNOWN_SHARDS` is an error.
+fn normalize_region(region: Option<String>) -> Result<Option<String>, String> {
+    let region = region
+        .map(|r| r.trim().to_ascii_lowercase())
+        .filter(|r| !r.is_empty());
+    if let Some(ref r) = region {
+        if !KNOWN_SHARDS.contains(&r.as_str()) {
+            return Err(format!("Unknown region '{}'", r));
+        }
+    }
+    Ok(region)
+}
+
+/// Fetch an account's storefront from just an `ssid` cookie and its shard.
+///
+/// The remaining cookies and the PUUID are recovered during auth. A session for
+/// a different PUUID than the account's stored one is refused. The result is
+/// cached and a rotated ssid is written back, as in `get_shop`, only when the
+/// PUUIDs are known to match; otherwise nothing is persisted.
+#[tauri::command]
+async fn get_shop_from_ssid(
+    app: tauri::AppHandle,
+    account_id: i64,
+    ssid: String,
+    shard: String,
+) -> Result<shop::Storefront, String> {
+    let ssid = ssid.trim().to_string();
+    if ssid.is_empty() {
+        return Err("ssid must not be empty".to_string());
+    }
+    let shard = shard.trim().to_ascii_lowercase();
+    if !KNOWN_SHARDS.contains(&shard.as_str()) {
+        return Err(format!("Unknown shard '{}'", shard));
+    }
+
+    let cookies = shop::RiotCookies {
+        asid: None,
+        ccid: None,
+        clid: None,
+        sub: None,
+        csid: None,
+        ssid: Some(ssid),
+        tdid: None,
+    };
+
+    let (mut storefront, updated_cookies) =
+        shop::fetch_storefront_in_region(cookies, Some(&shard))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let stored_puuid = get_account_puuid(account_id)?;
+    let session_puuid = updated_cookies.sub.clone().filter(|s| !s.is_empty());
+    match (&stored_puuid, &session_puuid) {
+        (Some(stored), Some(session)) if !stored.eq_ignore_ascii_case(session) => {
+            log::warn!(
+                "get_shop_from_ssid: ssid belongs to {} but account {} is {}",
+                session,
+                account_id,
+                stored
+            );
+            return Err("This ssid belongs to a different Riot account".to_string());
+        }
+        (Some(_), Some(_)) => {
+            shop::save_storefront_cache(account_id, &storefront);
+            shop::record_storefront_history(account_id, &storefront);
+            write_back_cookies(account_id, &updated_cookies);
+            emit_bundle_discount_alerts(&app, account_id, &storefront);
+        }
+        _ => log::info!(
+            "get_shop_from_ssid: cannot confirm the session is account {}'s, not persisting",
+            account_id
+        ),
+    }
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// Fetch the storefront like `get_shop`, but never write anything back.
+///
+/// A valid cache entry is still returned, but a live fetch is neither cached
+/// nor allowed to rewrite the account's RiotGamesPrivateSettings.yaml.
+#[tauri::command]
+async fn peek_shop(account_id: i64, cookies: shop::RiotCookies) -> Result<shop::Storefront, String> {
+    log::debug!("peek_shop: called for account {}", account_id);
+
+    let mut storefront = match shop::load_cached_storefront(account_id) {
+        Some(cached) => {
+            log::debug!("peek_shop: returning cached storefront for account {}", account_id);
+            cached
+        }
+        None => {
+            let region = account_region(account_id)?;
+            let (storefront, _) = shop::fetch_storefront_in_region(cookies, region.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            log::debug!("peek_shop: storefront fetched, skipping cache and cookie write-back");
+            storefront
+        }
+    };
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// Export an account's shop as structured JSON for rendering a shareable card.
+///
+/// Uses the cached storefront when available and only fetches on a cache miss.
+/// Names and icons come from the local skins database.
+#[tauri::command]
+async fn export_shop_json(
+    app: tauri::AppHandle,
+    account_id: i64,
+) -> Result<shop::ShopExport, String> {
+    let storefront = storefront_for_account(&app, account_id).await?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    let mut uuids: Vec<String> = storefront
+        .daily_offers
+        .iter()
+        .map(|o| o.skin_uuid.clone())
+        .collect();
+    uuids.extend(storefront.night_market.iter().flatten().map(|o| o.skin_uuid.clone()));
+    uuids.extend(
+        storefront
+            .bundles
+            .iter()
+            .flatten()
+            .flat_map(|b| b.items.iter().map(|i| i.item_uuid.clone())),
+    );
+
+    let displays = item_displays(uuids)?;
+
+    Ok(shop::build_shop_export(account_id, &storefront, now, |uuid| {
+        displays.get(uuid).cloned()
+    }))
+}
+
+/// Compare two accounts' daily offers: skins unique to each and the ones in common.
+#[tauri::command]
+async fn compare_shops(
+    app: tauri::AppHandle,
+    account_a: i64,
+    account_b: i64,
+) -> Result<shop::ShopComparison, String> {
+    let a = storefront_for_account(&app, account_a).await?;
+    let b = storefront_for_account(&app, account_b).await?;
+
+    let uuids = a
+        .daily_offers
+        .iter()
+        .chain(&b.daily_offers)
+        .map(|o| o.skin_uuid.clone())
+        .collect();
+    let displays = item_displays(uuids)?;
+
+    Ok(shop::compare_storefronts(&a, &b, |uuid| displays.get(uuid).cloned()))
+}
+
+/// The account's cached storefront, fetched with its stored cookies on a cache miss.
+///
+/// Costs are blanked when `show_vp_costs` is off, so exports and comparisons never carry them.
+async fn storefront_for_account(
+    app: &tauri::AppHandle,
+    account_id: i64,
+) -> Result<shop::Storefront, String> {
+    let mut storefront = match shop::load_cached_storefront(account_id) {
+        Some(cached) => cached,
+        None => {
+            let cookies = get_account_cookies(account_id)?
+                .ok_or("No stored session for this account")?;
+            load_or_fetch_storefront(app, account_id, cookies).await?
+        }
+    };
+
+    if !get_settings()?.show_vp_costs {
+        shop::hide_costs(&mut storefront);
+    }
+
+    Ok(storefront)
+}
+
+/// Resolve names and icons for item UUIDs from the skins database.
+fn item_displays(
+    uuids: Vec<String>,
+) -> Result<std::collections::HashMap<String, shop::ItemDisplay>, String> {
+    let resolved = skins::resolve_items(&uuids).map_err(|e| e.to_string())?;
+
+    Ok(uuids
+        .into_iter()
+        .zip(resolved)
+        .filter_map(|(uuid, item)| {
+            let item = item?;
+            Some((
+                uuid,
+                shop::ItemDisplay {
+                    name: item.display_name().map(str::to_string),
+                    icon: item.display_icon().map(str::to_string),
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Equipped loadout with each gun's skin level resolved from the skins database.
+///
+/// `skins` is aligned with `loadout.guns`; unknown levels are `None`.
+#[derive(serde::Serialize)]
+struct ResolvedLoadout {
+    #[serde(flatten)]
+    loadout: shop::Loadout,
+    skins: Vec<Option<skins::SkinWeapon>>,
+}
+
+/// Fetch the account's currency balances. Rotated cookies are written back as in `get_shop`.
+#[tauri::command]
+async fn get_wallet(account_id: i64, cookies: shop::RiotCookies) -> Result<shop::Wallet, String> {
+    let region = account_region(account_id)?;
+    let (wallet, updated_cookies) = shop::fetch_wallet(cookies, region.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    write_back_cookies(account_id, &updated_cookies);
+    Ok(wallet)
+}
+
+/// Read the Riot ID the account is currently logged in as.
+///
+/// The stored `riot_id`/`tagline` are user-entered and drift after a rename, so
+/// the UI compares them against this. Names are `None` for tokens without an `acct` claim.
+#[tauri::command]
+async fn verify_account_identity(
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<shop::RiotIdentity, String> {
+    let (identity, updated_cookies) = shop::fetch_identity(cookies)
+        .await
+        .map_err(|e| e.to_string())?;
+    write_back_cookies(account_id, &updated_cookies);
+    Ok(identity)
+}
+
+/// Fetch the account's currently equipped loadout, cached briefly per account.
+#[tauri::command]
+async fn get_loadout(account_id: i64, cookies: shop::RiotCookies) -> Result<ResolvedLoadout, String> {
+    let loadout = match shop::load_cached_loadout(account_id) {
+        Some(cached) => cached,
+        None => {
+            let region = account_region(account_id)?;
+            let (loadout, updated_cookies) = shop::fetch_loadout(cookies, region.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            shop::save_loadout_cache(account_id, &loadout);
+            write_back_cookies(account_id, &updated_cookies);
+            loadout
+        }
+    };
+
+    let level_uuids: Vec<String> = loadout
+        .guns
+        .iter()
+        .map(|g| g.skin_level_uuid.clone())
+        .collect();
+    let skins = skins::get_skins_by_level_uuids(&level_uuids).map_err(|e| e.to_string())?;
+
+    Ok(ResolvedLoadout { loadout, skins })
+}
+
+/// List the agents the account has unlocked, including the free starter roster.
+///
+/// Entitlements are cached per account; unknown UUIDs (agents newer than the
+/// synced skins DB) are dropped.
+#[tauri::command]
+async fn get_owned_agents(
+    account_id: i64,
+    cookies: shop::RiotCookies,
+) -> Result<Vec<skins::AgentItem>, String> {
+    let owned_uuids = match shop::load_cached_owned_agents(account_id) {
+        Some(cached) => cached,
+        None => {
+            let region = account_region(account_id)?;
+            let (uuids, updated_cookies) = shop::fetch_owned_agents(cookies, region.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            shop::save_owned_agents_cache(account_id, &uuids);
+            write_back_cookies(account_id, &updated_cookies);
+            uuids
+        }
+    };
+
+    let mut agents = skins::get_base_agents().map_err(|e| e.to_string())?;
+    let owned = skins::get_agents_by_uuids(&owned_uuids).map_err(|e| e.to_string())?;
+    for agent in owned.into_iter().flatten() {
+        if !agents.iter().any(|a| a.uuid == agent.uuid) {
+            agents.push(agent);
+        }
+    }
+    agents.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+
+    Ok(agents)
+}
+
+/// Reset timers for every store in the account's cached storefront.
+///
+/// Returns `None` when the account has no valid cached storefront.
+#[tauri::command]
+fn get_store_timers(account_id: i64) -> Option<shop::StoreTimers> {
+    shop::load_cached_storefront(account_id).map(|storefront| shop::store_timers(&storefront))
+}
+
+/// Seconds until the next daily shop rotation at 00:00 UTC, shared by all accounts.
+#[tauri::command]
+fn get_global_shop_reset() -> u64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    shop::secs_until_daily_reset(now)
+}
+
+/// Fetch a storefront from pasted cookies without touching any account, cache, or yaml.
+///
+/// A blank `region` falls back to the shard derived from `clid`.
+#[tauri::command]
+async fn get_shop_raw(
+    cookies: shop::RiotCookies,
+    region: Option<String>,
+) -> Result<shop::Storefront, String> {
+    let region = normalize_region(region)?;
+    let (storefront, _) = shop::fetch_storefront_in_region(cookies, region.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(storefront)
+}
+
+/// List every account with a cached storefront and whether it is still fresh.
+#[tauri::command]
+fn list_cached_shops() -> Result<Vec<shop::CachedShopInfo>, String> {
+    shop::list_cached_storefronts()
+}
+
+/// Drop the cached storefront of one account, or of all accounts when `account_id` is omitted.
+///
+/// The next `get_shop` refetches from Riot. Returns the number of entries removed.
+#[tauri::command]
+fn clear_shop_cache(account_id: Option<i64>) -> Result<usize, String> {
+    shop::clear_storefront_cache(account_id)
+}
+
+/// Summarize VP savings from the account's cached night market.
+///
+/// Returns `None` when there is no cached storefront or no active night market.
+#[tauri::command]
+fn get_night_market_savings(account_id: i64) -> Option<shop::NightMarketSavings> {
+    let storefront = shop::load_cached_storefront(account_id)?;
+    // The cached offers outlive the night market itself; only count an active one
+    if storefront.night_market_remaining_secs.is_none() {
+        return None;
+    }
+    shop::night_market_savings(storefront.night_market.as_deref()?)
+}
+
+/// An account whose cached night market offers a given skin.
+#[derive(serde::Serialize)]
+struct NightMarketMatch {
+    account_id: i64,
+    riot_id: String,
+    tagline: String,
+    offer: shop::NightMarketOffer,
+    remaining_secs: u64,
+}
+
+/// Find accounts whose active cached night market offers `skin_uuid`.
+///
+/// `skin_uuid` may be either the skin or one of its level UUIDs. No network calls.
+#[tauri::command]
+fn find_night_market_skin(skin_uuid: String) -> Result<Vec<NightMarketMatch>, String> {
+    let mut candidates = Vec::new();
+    for account in get_all_accounts()? {
+        let Some(storefront) = shop::load_cached_storefront(account.id) else {
+            continue;
+        };
+        let (Some(offers), Some(remaining_secs)) =
+            (storefront.night_market, storefront.night_market_remaining_secs)
+        else {
+            continue;
+        };
+        for offer in offers {
+            candidates.push((account.clone(), offer, remaining_secs));
+        }
+    }
+
+    let level_uuids: Vec<String> = candidates
+        .iter()
+        .map(|(_, offer, _)| offer.skin_uuid.clone())
+        .collect();
+    let skins = skins::get_skins_by_level_uuids(&level_uuids).map_err(|e| e.to_string())?;
+
+    let matches = candidates
+        .into_iter()
+        .zip(skins)
+        .filter(|((_, offer, _), skin)| {
+            offer.skin_uuid.eq_ignore_ascii_case(&skin_uuid)
+                || skin
+                    .as_ref()
+                    .is_some_and(|s| s.uuid.eq_ignore_ascii_case(&skin_uuid))
+        })
+        .map(|((account, offer, remaining_secs), _)| NightMarketMatch {
+            account_id: account.id,
+            riot_id: account.riot_id,
+            tagline: account.tagline,
+            offer,
+            remaining_secs,
+        })
+        .collect();
+
+    Ok(matches)
+}
+
+#[tauri::command]
+fn get_skin_info(level_uuid: String) -> Result<Option<skins::SkinWeapon>, String> {
+    skins::get_skin_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
+}
+
+/// Shortest UUID prefix `find_skin_by_uuid_prefix` accepts.
+const MIN_UUID_PREFIX_LEN: usize = 6;
+/// Most skins `find_skin_by_uuid_prefix` returns.
+const MAX_UUID_PREFIX_MATCHES: usize = 20;
+
+/// Debugging aid: find skins whose weapon or level UUID starts with `prefix`,
+/// for correlating truncated UUIDs in logs with actual skins.
+#[tauri::command]
+fn find_skin_by_uuid_prefix(prefix: String) -> Result<Vec<skins::SkinWeapon>, String> {
+    let prefix = prefix.trim();
+    if prefix.len() < MIN_UUID_PREFIX_LEN {
+        return Err(format!(
+            "UUID prefix must be at least {} characters",
+            MIN_UUID_PREFIX_LEN
+        ));
+    }
+    skins::find_skins_by_uuid_prefix(prefix, MAX_UUID_PREFIX_MATCHES).map_err(|e| e.to_string())
+}
+
+/// Estimated VP price of a skin from its content tier.
+///
+/// This is the canonical tier price, not the live shop cost; `None` for unknown
+/// skins and for tiers without a fixed price (Exclusive).
+#[tauri::command]
+fn get_skin_price_estimate(weapon_uuid: String) -> Result<Option<u64>, String> {
+    let skin = skins::get_skin_by_uuid(&weapon_uuid).map_err(|e| e.to_string())?;
+    Ok(skin
+        .and_then(|s| s.tier_uuid)
+        .and_then(|uuid| skins::estimated_price_for_tier(&uuid)))
+}
+
+/// Estimated VP value of a list of skins, summed by content tier.
+#[tauri::command]
+fn estimate_collection_value(weapon_uuids: Vec<String>) -> Result<skins::CollectionValue, String> {
+    let tier_uuids: Vec<Option<String>> = skins::get_skins_by_uuids(&weapon_uuids)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|skin| skin.and_then(|s| s.tier_uuid))
+        .collect();
+    Ok(skins::collection_value(&tier_uuids))
+}
+
+#[tauri::command]
+fn get_skin_info_batch(level_uuids: Vec<String>) -> Result<Vec<Option<skins::SkinWeapon>>, String> {
+    skins::get_skins_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_buddy_info(level_uuid: String) -> Result<Option<skins::BuddyItem>, String> {
+    skins::get_buddy_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_buddy_info_batch(
+    level_uuids: Vec<String>,
+) -> Result<Vec<Option<skins::BuddyItem>>, String> {
+    skins::get_buddies_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_flex_info(uuid: String) -> Result<Option<skins::FlexItem>, String> {
+    skins::get_flex_by_uuid(&uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_flex_info_batch(uuids: Vec<String>) -> Result<Vec<Option<skins::FlexItem>>, String> {
+    skins::get_flex_by_uuids(&uuids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_playercard_info(uuid: String) -> Result<Option<skins::PlayercardItem>, String> {
+    skins::get_playercard_by_uuid(&uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_playercard_info_batch(
+    uuids: Vec<String>,
+) -> Result<Vec<Option<skins::PlayercardItem>>, String> {
+    skins::get_playercards_by_uuids(&uuids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_spray_info(level_uuid: String) -> Result<Option<skins::SprayItem>, String> {
+    skins::get_spray_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_spray_info_batch(
+    level_uuids: Vec<String>,
+) -> Result<Vec<Option<skins::SprayItem>>, String> {
+    skins::get_sprays_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_title_info(uuid: String) -> Result<Option<skins::TitleItem>, String> {
+    skins::get_title_by_uuid(&uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_title_info_batch(uuids: Vec<String>) -> Result<Vec<Option<skins::TitleItem>>, String> {
+    skins::get_titles_by_uuids(&uuids).map_err(|e| e.to_string())
+}
+
+/// Skins database versions synced so far, newest first.
+#[tauri::command]
+fn get_version_history() -> Result<Vec<skins::VersionHistoryEntry>, String> {
+    skins::get_version_history().map_err(|e| e.to_string())
+}
+
+/// List all content tiers with their display names, ordered by rank.
+#[tauri::command]
+fn get_all_content_tiers() -> Result<Vec<skins::ContentTier>, String> {
+    skins::get_all_content_tiers().map_err(|e| e.to_string())
+}
+
+/// Resolve a shop item UUID without knowing its type up front.
+#[tauri::command]
+fn resolve_item(uuid: String) -> Result<Option<skins::ResolvedItem>, String> {
+    skins::resolve_item(&uuid).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn resolve_item_batch(uuids: Vec<String>) -> Result<Vec<Option<skins::ResolvedItem>>, String> {
+    skins::resolve_items(&uuids).map_err(|e| e.to_string())
+}
+
+/// Icon URL of a cosmetic, tagged with the item type it resolved to.
+#[derive(serde::Serialize)]
+struct ItemIcon {
+    item_type: &'static str,
+    icon: String,
+}
+
+fn item_icon(item: Option<skins::ResolvedItem>) -> Option<ItemIcon> {
+    let item = item?;
+    Some(ItemIcon {
+        item_type: item.item_type(),
+        icon: item.display_icon()?.to_string(),
+    })
+}
+
+/// Icon of any cosmetic UUID. `None` when unknown or when the item has no icon (titles).
+#[tauri::command]
+fn get_item_icon(uuid: String) -> Result<Option<ItemIcon>, String> {
+    skins::resolve_item(&uuid)
+        .map(item_icon)
+        .map_err(|e| e.to_string())
+}
+
+/// Batch form of `get_item_icon`; results are aligned with `uuids`.
+#[tauri::command]
+fn get_item_icon_batch(uuids: Vec<String>) -> Result<Vec<Option<ItemIcon>>, String> {
+    let resolved = skins::resolve_items(&uuids).map_err(|e| e.to_string())?;
+    Ok(resolved.into_iter().map(item_icon).collect())
+}
+
+#[tauri::command]
+async fn sync_skins() -> Result<skins::SyncReport, String> {
+    skins::sync_skins_database()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop a running skins sync before its next table; finished tables are kept.
+#[tauri::command]
+fn cancel_skins_sync() {
+    skins::cancel_sync();
+}
+
+/// Per-table emptiness of the skins database, plus whether any table is empty.
+#[derive(serde::Serialize)]
+struct SkinsTableStatus {
+    #[serde(flatten)]
+    tables: skins::TableStatus,
+    any_empty: bool,
+}
+
+#[tauri::command]
+fn get_skins_table_status() -> Result<SkinsTableStatus, String> {
+    let tables = skins::get_table_status().map_err(|e| e.to_string())?;
+    let any_empty = tables.any_empty();
+    Ok(SkinsTableStatus { tables, any_empty })
+}
+
+/// Re-fetch only the empty skins tables without forcing a full resync.
+#[tauri::command]
+async fn sync_empty_skins_tables() -> Result<skins::SyncReport, String> {
+    skins::sync_empty_tables()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn open_shop_window(app: tauri::AppHandle, account_id: i64, title: String) -> Result<(), String> {
+    let label = format!("shop-{}", account_id);
+
+    if let Some(existing) = app.get_webview_window(&label) {
+        existing.set_focus().map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    tauri::WebviewWindowBuilder::new(
+        &app,
+        label,
+        tauri::WebviewUrl::App(std::path::PathBuf::from("/")),
+    )
+    .title(title)
+    .inner_size(1200.0, 650.0)
+    .min_inner_size(960.0, 600.0)
+    .build()
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+fn switch_account(account_id: Option<i64>) -> Result<(), String> {
+    switch_account_checked(account_id)?;
+    Ok(())
+}
+
+/// Switch accounts unless Riot Client or Valorant is running; returns the new junction target.
+fn switch_account_checked(account_id: Option<i64>) -> Result<PathBuf, String> {
+    log::info!("Starting account switch: {:?}", account_id);
+
+    if process::check_riot_client_running() {
+        log::warn!("Cannot switch accounts: Riot Client is running");
+        return Err("Cannot switch accounts while Riot Client is running".to_string());
+    }
+    if process::check_valorant_running() {
+        log::warn!("Cannot switch accounts: Valorant is running");
+        return Err("Cannot switch accounts while Valorant is running".to_string());
+    }
+
+    let target = perform_account_switch(account_id)?;
+
+    log::info!("Account switch completed successfully");
+    Ok(target)
+}
+
+/// Drive type of a directory the Riot data junction points into.
+#[derive(Clone, serde::Serialize)]
+struct DataDriveStatus {
+    path: String,
+    drive_type: fs::DriveType,
+    /// Removable or network drive: Riot sees a fresh login whenever it disconnects.
+    detachable: bool,
+}
+
+fn data_drive_status(path: &std::path::Path) -> DataDriveStatus {
+    let drive_type = fs::drive_type(path);
+    DataDriveStatus {
+        path: path.to_string_lossy().to_string(),
+        drive_type,
+        detachable: drive_type.is_detachable(),
+    }
+}
+
+fn detachable_drive_status(path: &std::path::Path) -> Option<DataDriveStatus> {
+    Some(data_drive_status(path)).filter(|s| s.detachable)
+}
+
+/// Log and emit `data-drive-warning` for a switch whose target is on a detachable drive.
+fn warn_detachable_drive(warning: DataDriveStatus) {
+    use tauri::Emitter;
+
+    log::warn!(
+        "Target directory {} is on a {:?} drive; the junction breaks if it disconnects",
+        warning.path,
+        warning.drive_type
+    );
+    if let Some(app) = APP_HANDLE.get() {
+        if let Err(e) = app.emit("data-drive-warning", warning) {
+            log::warn!("Failed to emit data-drive-warning: {}", e);
+        }
+    }
+}
+
+/// How deep `list_data_path_junctions` looks below the account data path.
+const JUNCTION_SCAN_DEPTH: usize = 4;
+
+/// A reparse point found by `list_data_path_junctions`.
+#[derive(serde::Serialize)]
+struct JunctionEntry {
+    path: String,
+    /// `None` when the target could not be read; see `error`.
+    target: Option<String>,
+    error: Option<String>,
+}
+
+fn junction_entry(path: &std::path::Path) -> JunctionEntry {
+    let (target, error) = match fs::get_junction_target(path) {
+        Ok(t) => (Some(t.to_string_lossy().to_string()), None),
+        Err(e) => (None, Some(e)),
+    };
+    JunctionEntry {
+        path: path.to_string_lossy().to_string(),
+        target,
+        error,
+    }
+}
+
+/// List every reparse point under the account data path, plus the Riot data path itself.
+///
+/// Only the Riot data path is expected to be a junction; anything else is
+/// usually left over from an interrupted switch.
+#[tauri::command]
+fn list_data_path_junctions() -> Result<Vec<JunctionEntry>, String> {
+    let settings = get_settings()?;
+    let riot_data_path = riot_data_root(&settings)?;
+    let account_data_path = account_data_root(&settings)?;
+
+    let mut entries = Vec::new();
+    if fs::is_symlink(&riot_data_path)? {
+        entries.push(junction_entry(&riot_data_path));
+    }
+    entries.extend(
+        fs::find_reparse_points(&account_data_path, JUNCTION_SCAN_DEPTH)
+            .iter()
+            .map(|p| junction_entry(p)),
+    );
+
+    log::info!("Found {} reparse points under the data paths", entries.len());
+    Ok(entries)
+}
+
+/// Compare data.db's accounts, settings and storefront_cache columns with the bundled schema.
+#[tauri::command]
+fn verify_db_schema() -> Result<Vec<db::init::TableDrift>, String> {
+    db::init::verify_db_schema()
+}
+
+/// Report the drive type of the account data directory, flagging removable and network drives.
+#[tauri::command]
+fn check_data_drive_type() -> Result<DataDriveStatus, String> {
+    let settings = get_settings()?;
+    Ok(data_drive_status(&account_data_root(&settings)?))
+}
+
+/// Check whether directory symlinks can be created without elevation.
+#[tauri::command]
+fn check_symlink_privilege() -> fs::SymlinkPrivilegeStatus {
+    fs::check_symlink_privilege()
+}
+
+/// Check that `path` can hold account data before it is saved as `account_data_path`.
+#[tauri::command]
+fn check_data_path_writable(path: String) -> fs::WritableStatus {
+    fs::check_dir_writable(&PathBuf::from(path))
+}
+
+/// Where `set_database_location` put the databases.
+#[derive(serde::Serialize)]
+struct DatabaseLocation {
+    db_path: String,
+    /// Why skins.db could not be moved; data.db has moved regardless.
+    skins_warning: Option<String>,
+}
+
+/// Move data.db, and skins.db when `move_skins` is set, into `path`.
+///
+/// `path` must be absolute. The location is remembered for future launches.
+/// A skins.db that fails to move, or is left behind, is simply re-synced into
+/// the new directory on the next start, so that failure is only a warning.
+#[tauri::command]
+fn set_database_location(path: String, move_skins: bool) -> Result<DatabaseLocation, String> {
+    // An account switch writes to the database; never move it underneath one
+    let _guard = SwitchGuard::acquire()?;
+
+    let dir = PathBuf::from(path.trim());
+    if !dir.is_absolute() {
+        return Err("Database location must be an absolute path".to_string());
+    }
+    let status = fs::check_dir_writable(&dir);
+    if !status.writable {
+        return Err(status.error.unwrap_or_else(|| format!("{} is not writable", dir.display())));
+    }
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let db_path = db::init::move_database(&dir)?;
+    let skins_warning = if move_skins {
+        skins::move_skins_db(&dir)
+            .map_err(|e| {
+                log::warn!("set_database_location: data.db moved but skins.db did not: {}", e);
+                e
+            })
+            .err()
+    } else {
+        None
+    };
+
+    Ok(DatabaseLocation {
+        db_path: db_path.to_string_lossy().to_string(),
+        skins_warning,
+    })
+}
+
+/// Move one account's data folder into `new_path` (e.g. on another drive).
+///
+/// The folder keeps its name and the account gets a `data_path_override`
+/// pointing at the new location. The junction is rebuilt if the account is active.
+#[tauri::command]
+fn move_account_data(account_id: i64, new_path: String) -> Result<db::models::Account, String> {
+    if process::check_riot_client_running() || process::check_valorant_running() {
+        return Err("Cannot move account data while Riot Client or Valorant is running".to_string());
+    }
+
+    // Held across the move and the junction rebuild so no switch runs in between
+    let guard = SwitchGuard::acquire()?;
+
+    let account = get_account(account_id)?;
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+
+    let current_dir = db::accounts::resolve_account_data_dir(&account, &account_data_path)
+        .ok_or("Account has no data directory assigned")?;
+    let folder_name = current_dir
+        .file_name()
+        .ok_or("Invalid account data directory")?
+        .to_owned();
+
+    let new_parent = PathBuf::from(&new_path);
+    if !new_parent.is_absolute() {
+        return Err("Destination must be an absolute path".to_string());
+    }
+    let new_dir = new_parent.join(folder_name);
+
+    if new_dir == current_dir {
+        return Ok(account);
+    }
+    if new_dir.exists() {
+        return Err(format!("Destination already exists: {}", new_dir.display()));
+    }
+
+    log::info!(
+        "Moving data for account {}: {} -> {}",
+        account_id,
+        current_dir.display(),
+        new_dir.display()
+    );
+
+    if current_dir.exists() {
+        // Copy-verify-delete works across volumes, unlike a plain rename
+        fs::move_directory_contents(&current_dir, &new_dir)?;
+        std::fs::remove_dir(&current_dir)
+            .map_err(|e| format!("Failed to remove old directory: {}", e))?;
+    } else {
+        fs::create_dir_with_marker(&new_dir)?;
+    }
+
+    let new_dir_str = new_dir.to_string_lossy().to_string();
+    let updated = db::accounts::set_data_path_override(account_id, Some(new_dir_str.as_str()))?;
+
+    if settings.active_account_id == Some(account_id) {
+        log::info!("Account {} is active, rebuilding junction", account_id);
+        switch_with_guard(&guard, Some(account_id))?;
+    }
+
+    Ok(updated)
+}
+
+/// Clear an account's stored Riot session so the next launch asks for a login.
+///
+/// With `backup` the yaml is renamed to `<name>.bak-<unix secs>` instead of deleted.
+#[tauri::command]
+fn reset_account_session(account_id: i64, backup: bool) -> Result<(), String> {
+    if process::check_riot_client_running() || process::check_valorant_running() {
+        return Err("Cannot reset session while Riot Client or Valorant is running".to_string());
+    }
+
+    let yaml_path = match resolve_account_yaml_path(account_id)? {
+        Some(path) => path,
+        None => {
+            log::info!("Account {} has no stored session to reset", account_id);
+            return Ok(());
+        }
+    };
+
+    if backup {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut backup_name = yaml_path.as_os_str().to_owned();
+        backup_name.push(format!(".bak-{}", secs));
+        let backup_path = PathBuf::from(backup_name);
+
+        std::fs::rename(&yaml_path, &backup_path)
+            .map_err(|e| format!("Failed to back up settings file: {}", e))?;
+        log::info!(
+            "Reset session for account {}: moved yaml to {}",
+            account_id,
+            backup_path.display()
+        );
     } else {
-        log::debug!("update_yaml_cookie_values: skipping tdid (no updated value)");
+        std::fs::remove_file(&yaml_path)
+            .map_err(|e| format!("Failed to delete settings file: {}", e))?;
+        log::info!("Reset session for account {}: deleted yaml", account_id);
     }
 
-    let changed = content != result;
-    log::debug!(
-        "update_yaml_cookie_values: done, content_changed={}",
-        changed
-    );
+    Ok(())
+}
 
-    result
+/// Files removed by `cleanup_account_temp_files`.
+#[derive(serde::Serialize)]
+struct TempFileCleanup {
+    removed: usize,
+    freed_bytes: u64,
 }
 
-fn save_account_cookies(account_id: i64, cookies: &shop::RiotCookies) -> Result<(), String> {
-    log::debug!("save_account_cookies: starting for account {}", account_id);
+/// Delete leftover `RiotGamesPrivateSettings.yaml.tmp` and `.bak-*` files from an account folder.
+#[tauri::command]
+fn cleanup_account_temp_files(account_id: i64) -> Result<TempFileCleanup, String> {
+    let account = get_account(account_id)?;
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
 
-    let yaml_path = match resolve_account_yaml_path(account_id)? {
-        Some(path) => {
-            log::debug!("save_account_cookies: resolved YAML path: {}", path.display());
-            path
-        }
-        None => {
-            log::info!(
-                "Skipping cookie save for account {}: YAML file does not exist",
-                account_id
-            );
-            return Ok(());
-        }
+    let data_dir = db::accounts::resolve_account_data_dir(&account, &account_data_path)
+        .ok_or("Account has no data directory assigned")?;
+
+    let mut cleanup = TempFileCleanup {
+        removed: 0,
+        freed_bytes: 0,
     };
 
-    let content = std::fs::read_to_string(&yaml_path)
-        .map_err(|e| format!("Failed to read settings file: {}", e))?;
-    log::debug!(
-        "save_account_cookies: read YAML file ({} bytes)",
-        content.len()
-    );
+    for path in find_yaml_leftovers(&data_dir) {
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                log::info!("Removed leftover file {}", path.display());
+                cleanup.removed += 1;
+                cleanup.freed_bytes += size;
+            }
+            Err(e) => log::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
 
-    let updated_content = update_yaml_cookie_values(&content, cookies);
+    Ok(cleanup)
+}
 
-    if content == updated_content {
-        log::debug!("save_account_cookies: no changes detected, skipping write");
-        return Ok(());
+/// Rebuild `_unselected` from a copy of an account's data folder.
+///
+/// A non-empty `_unselected` is only replaced when `overwrite` is set. The copied
+/// account marker is swapped for the `_unselected` one.
+#[tauri::command]
+fn seed_unselected_from(account_id: i64, overwrite: bool) -> Result<(), String> {
+    if process::check_riot_client_running() || process::check_valorant_running() {
+        return Err("Cannot rebuild _unselected while Riot Client or Valorant is running".to_string());
     }
 
-    // Atomic write: write to a temp file, then rename over the original
-    let tmp_path = yaml_path.with_extension("yaml.tmp");
-    log::debug!(
-        "save_account_cookies: writing {} bytes to temp file: {}",
-        updated_content.len(),
-        tmp_path.display()
-    );
-    std::fs::write(&tmp_path, &updated_content)
-        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    // A switch to `_unselected` must not run while it is being replaced
+    let _guard = SwitchGuard::acquire()?;
 
-    log::debug!("save_account_cookies: renaming temp file to YAML path");
-    std::fs::rename(&tmp_path, &yaml_path)
-        .map_err(|e| format!("Failed to rename temp file: {}", e))?;
+    let account = get_account(account_id)?;
+    let settings = get_settings()?;
+    let account_data_path = account_data_root(&settings)?;
+
+    let source = db::accounts::resolve_account_data_dir(&account, &account_data_path)
+        .ok_or("Account has no data directory assigned")?;
+    if !source.is_dir() {
+        return Err(format!("Account data folder not found: {}", source.display()));
+    }
+
+    let unselected = account_data_path.join("_unselected");
+    if unselected.exists() {
+        let has_data = std::fs::read_dir(&unselected)
+            .map_err(|e| format!("Failed to read _unselected: {}", e))?
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name() != "_unselected");
+        if has_data && !overwrite {
+            return Err("_unselected is not empty; confirm to overwrite it".to_string());
+        }
+
+        log::info!("Clearing existing _unselected: {}", unselected.display());
+        std::fs::remove_dir_all(&unselected)
+            .map_err(|e| format!("Failed to clear _unselected: {}", e))?;
+    }
 
     log::info!(
-        "save_account_cookies: successfully saved updated cookies for account {}",
-        account_id
+        "Seeding _unselected from account {}: {}",
+        account_id,
+        source.display()
     );
+    fs::copy_dir_recursive(&source, &unselected)?;
+
+    if let Some(folder) = source.file_name() {
+        let copied_marker = unselected.join(folder);
+        if copied_marker.metadata().is_ok_and(|m| m.is_file() && m.len() == 0) {
+            let _ = std::fs::remove_file(&copied_marker);
+        }
+    }
+    fs::create_dir_with_marker(&unselected)?;
+
     Ok(())
 }
 
-/// Fetch the daily shop and night market, returning a cached result when valid.
+/// Delete an account, its caches and its data folder.
+///
+/// Refused while Riot Client or Valorant is running, since the folder may be in use.
 #[tauri::command]
-async fn get_shop(account_id: i64, cookies: shop::RiotCookies) -> Result<shop::Storefront, String> {
-    log::debug!("get_shop: called for account {}", account_id);
+fn delete_account(account_id: i64) -> Result<(), String> {
+    if process::check_riot_client_running() {
+        return Err("Cannot delete accounts while Riot Client is running".to_string());
+    }
+    if process::check_valorant_running() {
+        return Err("Cannot delete accounts while Valorant is running".to_string());
+    }
 
-    if let Some(cached) = shop::load_cached_storefront(account_id) {
-        log::debug!("get_shop: returning cached storefront for account {}", account_id);
-        return Ok(cached);
+    log::info!("Deleting account {}", account_id);
+    remove_account(account_id)
+}
+
+/// Delete an account and its data folder, switching away first if it is active.
+///
+/// The folder is kept when another account resolves to the same directory,
+/// which older databases without the unique `data_folder` index can contain.
+fn remove_account(account_id: i64) -> Result<(), String> {
+    let account = get_account(account_id)?;
+    let settings = get_settings()?;
+
+    if settings.active_account_id == Some(account_id) {
+        log::info!("Account {} is active, switching to unselected before removal", account_id);
+        switch_account_checked(None)?;
     }
 
-    log::debug!("get_shop: no cache, fetching storefront for account {}", account_id);
-    let (storefront, updated_cookies) = shop::fetch_storefront(cookies)
-        .await
-        .map_err(|e| e.to_string())?;
+    let account_data_path = account_data_root(&settings)?;
+    let data_dir = db::accounts::resolve_account_data_dir(&account, &account_data_path);
+    let shared = match &data_dir {
+        Some(dir) => get_all_accounts()?.iter().any(|other| {
+            other.id != account_id
+                && db::accounts::resolve_account_data_dir(other, &account_data_path)
+                    .is_some_and(|other_dir| same_path(&other_dir, dir))
+        }),
+        None => false,
+    };
 
-    log::debug!("get_shop: storefront fetched, saving cache");
-    shop::save_storefront_cache(account_id, &storefront);
+    db::accounts::delete_account(account_id)?;
 
-    log::debug!("get_shop: persisting updated cookies to YAML");
-    if let Err(e) = save_account_cookies(account_id, &updated_cookies) {
-        log::warn!("Failed to save updated cookies for account {}: {}", account_id, e);
+    if shared {
+        log::warn!(
+            "Keeping data folder of account {}: another account uses the same directory",
+            account_id
+        );
+        return Ok(());
     }
 
-    Ok(storefront)
-}
+    if let Some(dir) = data_dir.filter(|d| d.exists()) {
+        log::info!("Removing data folder: {}", dir.display());
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to remove data folder {}: {}", dir.display(), e))?;
+    }
 
-#[tauri::command]
-fn get_skin_info(level_uuid: String) -> Result<Option<skins::SkinWeapon>, String> {
-    skins::get_skin_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
+    Ok(())
 }
 
+/// Merge `remove_id` into `keep_id` and delete the removed account.
+///
+/// Empty fields on the kept account (username, password, rank, region) are
+/// filled from the removed one; the kept account's values win otherwise.
 #[tauri::command]
-fn get_skin_info_batch(level_uuids: Vec<String>) -> Result<Vec<Option<skins::SkinWeapon>>, String> {
-    skins::get_skins_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
-}
+fn merge_accounts(keep_id: i64, remove_id: i64) -> Result<db::models::Account, String> {
+    if keep_id == remove_id {
+        return Err("Cannot merge an account into itself".to_string());
+    }
 
-#[tauri::command]
-fn get_buddy_info(level_uuid: String) -> Result<Option<skins::BuddyItem>, String> {
-    skins::get_buddy_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
-}
+    log::info!("Merging account {} into {}", remove_id, keep_id);
+    db::accounts::merge_account_fields(keep_id, remove_id)?;
+    remove_account(remove_id)?;
 
-#[tauri::command]
-fn get_buddy_info_batch(
-    level_uuids: Vec<String>,
-) -> Result<Vec<Option<skins::BuddyItem>>, String> {
-    skins::get_buddies_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
+    get_account(keep_id)
 }
 
+/// Switch to the account with the given Riot ID (case-insensitive).
 #[tauri::command]
-fn get_flex_info(uuid: String) -> Result<Option<skins::FlexItem>, String> {
-    skins::get_flex_by_uuid(&uuid).map_err(|e| e.to_string())
+fn switch_account_by_name(riot_id: String, tagline: String) -> Result<(), String> {
+    let tagline = tagline.trim_start_matches('#');
+    let matches = db::accounts::find_accounts_by_riot_id(riot_id.trim(), tagline.trim())?;
+
+    match matches.as_slice() {
+        [account] => switch_account(Some(account.id)),
+        [] => Err(format!("No account named {}#{}", riot_id, tagline)),
+        _ => Err(format!(
+            "{} accounts are named {}#{}; switch by id instead",
+            matches.len(),
+            riot_id,
+            tagline
+        )),
+    }
 }
 
+/// Switch back to the account that was active before the current one.
 #[tauri::command]
-fn get_flex_info_batch(uuids: Vec<String>) -> Result<Vec<Option<skins::FlexItem>>, String> {
-    skins::get_flex_by_uuids(&uuids).map_err(|e| e.to_string())
-}
+fn switch_to_previous() -> Result<(), String> {
+    let settings = get_settings()?;
+    let previous = settings
+        .previous_account_id
+        .ok_or("No previous account recorded")?;
 
-#[tauri::command]
-fn get_playercard_info(uuid: String) -> Result<Option<skins::PlayercardItem>, String> {
-    skins::get_playercard_by_uuid(&uuid).map_err(|e| e.to_string())
+    log::info!("Switching back to previous account {}", previous);
+    switch_account(Some(previous))
 }
 
-#[tauri::command]
-fn get_playercard_info_batch(
-    uuids: Vec<String>,
-) -> Result<Vec<Option<skins::PlayercardItem>>, String> {
-    skins::get_playercards_by_uuids(&uuids).map_err(|e| e.to_string())
-}
+/// Re-apply the last active account on startup when the user opted in.
+fn auto_select_last_account() {
+    let settings = match get_settings() {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Auto-select: failed to read settings: {}", e);
+            return;
+        }
+    };
 
-#[tauri::command]
-fn get_spray_info(level_uuid: String) -> Result<Option<skins::SprayItem>, String> {
-    skins::get_spray_by_level_uuid(&level_uuid).map_err(|e| e.to_string())
-}
+    if !settings.auto_select_last_account {
+        return;
+    }
 
-#[tauri::command]
-fn get_spray_info_batch(
-    level_uuids: Vec<String>,
-) -> Result<Vec<Option<skins::SprayItem>>, String> {
-    skins::get_sprays_by_level_uuids(&level_uuids).map_err(|e| e.to_string())
+    let Some(account_id) = settings.active_account_id else {
+        log::info!("Auto-select: no last-used account recorded");
+        return;
+    };
+
+    if process::check_riot_client_running() || process::check_valorant_running() {
+        log::info!("Auto-select: skipped because Riot Client or Valorant is running");
+        return;
+    }
+
+    match perform_account_switch(Some(account_id)) {
+        Ok(_) => log::info!("Auto-select: switched to last-used account {}", account_id),
+        Err(e) => log::warn!("Auto-select: failed to switch to account {}: {}", account_id, e),
+    }
 }
 
-#[tauri::command]
-async fn sync_skins() -> Result<bool, String> {
-    skins::sync_skins_database()
-        .await
-        .map_err(|e| e.to_string())
+/// Outcome of the startup shop prefetch, emitted as `prefetch-complete`.
+#[derive(Clone, serde::Serialize)]
+struct PrefetchSummary {
+    fetched: usize,
+    cached: usize,
+    skipped: usize,
+    failed: usize,
 }
 
-#[tauri::command]
-async fn open_shop_window(app: tauri::AppHandle, account_id: i64, title: String) -> Result<(), String> {
-    let label = format!("shop-{}", account_id);
+/// Warm `storefront_cache` for every account with a stored session.
+///
+/// Accounts are fetched one at a time so startup never hammers the Riot API.
+async fn prefetch_shops(app: tauri::AppHandle) {
+    use tauri::Emitter;
+
+    let accounts = match get_all_accounts() {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            log::warn!("Prefetch: failed to list accounts: {}", e);
+            return;
+        }
+    };
 
-    if let Some(existing) = app.get_webview_window(&label) {
-        existing.set_focus().map_err(|e| e.to_string())?;
-        return Ok(());
+    let mut summary = PrefetchSummary { fetched: 0, cached: 0, skipped: 0, failed: 0 };
+
+    for account in accounts {
+        if shop::load_cached_storefront(account.id).is_some() {
+            summary.cached += 1;
+            continue;
+        }
+
+        let cookies = match get_account_cookies(account.id) {
+            Ok(Some(cookies)) => cookies,
+            Ok(None) => {
+                summary.skipped += 1;
+                continue;
+            }
+            Err(e) => {
+                log::debug!("Prefetch: skipping account {}: {}", account.id, e);
+                summary.skipped += 1;
+                continue;
+            }
+        };
+
+        match load_or_fetch_storefront(&app, account.id, cookies).await {
+            Ok(_) => summary.fetched += 1,
+            Err(e) => {
+                log::warn!("Prefetch: failed to fetch shop for account {}: {}", account.id, e);
+                summary.failed += 1;
+            }
+        }
     }
 
-    tauri::WebviewWindowBuilder::new(
-        &app,
-        label,
-        tauri::WebviewUrl::App(std::path::PathBuf::from("/")),
-    )
-    .title(title)
-    .inner_size(1200.0, 650.0)
-    .min_inner_size(960.0, 600.0)
-    .build()
-    .map_err(|e| e.to_string())?;
+    log::info!(
+        "Prefetch: {} fetched, {} already cached, {} skipped, {} failed",
+        summary.fetched,
+        summary.cached,
+        summary.skipped,
+        summary.failed
+    );
 
-    Ok(())
+    if let Err(e) = app.emit("prefetch-complete", summary) {
+        log::warn!("Failed to emit prefetch-complete: {}", e);
+    }
 }
 
-#[tauri::command]
-fn switch_account(account_id: Option<i64>) -> Result<(), String> {
-    log::info!("Starting account switch: {:?}", account_id);
+/// Startup actions requested on the command line, e.g. from a per-account shortcut.
+#[derive(Debug, Default, PartialEq)]
+struct CliOptions {
+    /// `--switch <account_id>`
+    switch_to: Option<i64>,
+    /// `--launch`: start the Riot Client after switching
+    launch: bool,
+    /// `--exit`: quit once the actions ran instead of showing the window
+    exit: bool,
+}
 
-    if process::check_riot_client_running() {
-        log::warn!("Cannot switch accounts: Riot Client is running");
-        return Err("Cannot switch accounts while Riot Client is running".to_string());
-    }
-    if process::check_valorant_running() {
-        log::warn!("Cannot switch accounts: Valorant is running");
-        return Err("Cannot switch accounts while Valorant is running".to_string());
+fn parse_cli_args<I: IntoIterator<Item = String>>(args: I) -> Result<CliOptions, String> {
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--switch" => {
+                let value = args.next().ok_or("--switch requires an account id")?;
+                let id = value
+                    .parse()
+                    .map_err(|_| format!("Invalid account id for --switch: {}", value))?;
+                options.switch_to = Some(id);
+            }
+            "--launch" => options.launch = true,
+            "--exit" => options.exit = true,
+            _ => {}
+        }
     }
 
-    perform_account_switch(account_id)?;
+    Ok(options)
+}
 
-    log::info!("Account switch completed successfully");
-    Ok(())
+/// Run the `--switch`/`--launch` actions. Failures are logged and stop later steps.
+fn run_cli_actions(options: &CliOptions) {
+    if let Some(account_id) = options.switch_to {
+        if let Err(e) = get_account(account_id).and_then(|_| switch_account_checked(Some(account_id))) {
+            log::error!("CLI: failed to switch to account {}: {}", account_id, e);
+            return;
+        }
+        log::info!("CLI: switched to account {}", account_id);
+    }
+
+    if options.launch {
+        match process::launch_riot_client() {
+            Ok(()) => log::info!("CLI: launched Riot Client"),
+            Err(e) => log::error!("CLI: failed to launch Riot Client: {}", e),
+        }
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -641,26 +3376,64 @@ pub fn run() {
         log::info!("Demo mode enabled");
     }
 
+    let cli = parse_cli_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        log::error!("Ignoring command line: {}", e);
+        CliOptions::default()
+    });
+
     if let Err(e) = initialize_database(None) {
         log::error!("Failed to initialize database: {}", e);
         eprintln!("Failed to initialize database: {}", e);
         std::process::exit(1);
     }
 
+    match get_settings() {
+        Ok(settings) => crypto::master::set_auto_lock_minutes(settings.auto_lock_minutes),
+        Err(e) => log::warn!("Failed to read auto-lock setting: {}", e),
+    }
+    crypto::master::start_auto_lock_timer();
+
     if let Err(e) = skins::initialize_skins_db(None) {
         log::error!("Failed to initialize skins database: {}", e);
     }
 
     tauri::Builder::default()
-        .setup(|app| {
-            process::start_process_monitor(app.handle().clone());
+        .setup(move |app| {
+            let _ = APP_HANDLE.set(app.handle().clone());
+
+            let monitor_config = get_settings()
+                .map(|s| process::ProcessMonitorConfig::from_settings(&s))
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to read process monitor settings: {}", e);
+                    process::ProcessMonitorConfig::default()
+                });
+            process::start_process_monitor(app.handle().clone(), monitor_config);
+            if cli.switch_to.is_some() || cli.launch {
+                run_cli_actions(&cli);
+                if cli.exit {
+                    app.handle().exit(0);
+                    return Ok(());
+                }
+            } else {
+                auto_select_last_account();
+            }
 
-            tauri::async_runtime::spawn(async {
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
                 match skins::sync_skins_database().await {
-                    Ok(true) => log::info!("Skins database synced successfully"),
-                    Ok(false) => log::info!("Skins database already up to date"),
+                    Ok(report) if !report.failed.is_empty() => log::warn!(
+                        "Skins database partially synced: updated {:?}, failed {:?}",
+                        report.updated,
+                        report.failed
+                    ),
+                    Ok(report) if !report.updated.is_empty() => log::info!("Skins database synced successfully"),
+                    Ok(_) => log::info!("Skins database already up to date"),
                     Err(e) => log::warn!("Failed to sync skins database: {}", e),
                 }
+
+                if get_settings().is_ok_and(|s| s.prefetch_shops_on_startup) {
+                    prefetch_shops(handle).await;
+                }
             });
 
             let window = app.get_webview_window("main")
@@ -680,18 +3453,91 @@ pub fn run() {
             add_account,
             list_accounts,
             edit_account,
+            delete_account,
             check_current_data_available,
             mark_launched,
             switch_account,
+            switch_to_previous,
+            switch_account_by_name,
+            toggle_favorite_account,
+            reorder_accounts,
+            add_wishlist_item,
+            remove_wishlist_item,
+            get_wishlist,
+            check_symlink_privilege,
+            check_junction_validity,
+            repair_active_junction,
+            check_data_path_writable,
+            check_data_drive_type,
+            list_data_path_junctions,
+            verify_db_schema,
+            diagnose_switch_readiness,
+            get_riot_login_state,
+            get_cookie_expiry,
+            audit_cookie_expiry,
+            set_database_location,
+            move_account_data,
             get_riot_client_status,
             kill_riot_client,
             launch_riot_client,
+            launch_valorant,
+            validate_riot_client_path,
             get_valorant_status,
+            refresh_process_status,
             copy_account_password,
+            reveal_account_password,
+            clear_account_password,
+            set_master_password,
+            clear_master_password,
+            unlock,
+            lock,
+            is_locked,
             get_account_cookies,
+            list_accounts_needing_login,
+            detect_account_region,
+            set_account_region,
+            rename_account_folder,
+            get_account_puuid,
+            find_duplicate_accounts,
+            export_account_list,
+            export_account_credential,
+            export_accounts,
+            import_accounts,
+            export_shop_history_csv,
+            get_shop_history,
+            merge_accounts,
+            audit_data_markers,
+            find_duplicate_folders,
+            get_account_data_sizes,
+            reset_account_session,
+            cleanup_account_temp_files,
+            seed_unselected_from,
+            preview_account_switch,
             get_shop,
+            get_active_shop,
+            refresh_shop,
+            diagnose_shop,
+            peek_shop,
+            get_shop_with_version,
+            get_shop_from_ssid,
+            get_shop_raw,
+            list_cached_shops,
+            clear_shop_cache,
+            verify_account_identity,
+            export_shop_json,
+            compare_shops,
+            get_global_shop_reset,
+            get_store_timers,
+            get_loadout,
+            get_wallet,
+            get_owned_agents,
+            get_night_market_savings,
+            find_night_market_skin,
             get_skin_info,
+            find_skin_by_uuid_prefix,
             get_skin_info_batch,
+            get_skin_price_estimate,
+            estimate_collection_value,
             get_buddy_info,
             get_buddy_info_batch,
             get_flex_info,
@@ -700,10 +3546,277 @@ pub fn run() {
             get_playercard_info_batch,
             get_spray_info,
             get_spray_info_batch,
+            get_title_info,
+            get_title_info_batch,
+            get_all_content_tiers,
+            get_version_history,
+            resolve_item,
+            resolve_item_batch,
+            get_item_icon,
+            get_item_icon_batch,
             sync_skins,
+            get_skins_table_status,
+            sync_empty_skins_tables,
+            cancel_skins_sync,
             open_shop_window,
             is_demo_mode
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_credential() {
+        let csv = format_credential("keepass_csv", "Player#JP1", "login", "pa\"ss").unwrap();
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "\"Player#JP1\",\"login\",\"pa\"\"ss\",\"https://account.riotgames.com\",\"Exported from ValoAccounts\""
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_credential("json", "Player#JP1", "login", "pw").unwrap())
+                .unwrap();
+        assert_eq!(json["username"], "login");
+        assert_eq!(json["password"], "pw");
+
+        assert!(format_credential("xml", "Player#JP1", "login", "pw").is_err());
+    }
+
+    fn settings_with_paths(riot_data: &std::path::Path, account_data: &std::path::Path) -> Settings {
+        Settings {
+            id: 1,
+            active_account_id: Some(3),
+            previous_account_id: None,
+            riot_client_service_path: None,
+            riot_client_data_path: Some(path_string(riot_data)),
+            account_data_path: Some(path_string(account_data)),
+            henrikdev_api_key: None,
+            region: None,
+            launched: true,
+            auto_select_last_account: false,
+            prefetch_shops_on_startup: false,
+            show_vp_costs: true,
+            encryption_mode: "machine".to_string(),
+            auto_lock_minutes: None,
+            theme: None,
+            accent_color: None,
+            compress_history: false,
+            bundle_discount_alert_threshold: None,
+            process_poll_secs: 2,
+            extra_watched_processes: vec![],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_plan_account_switch_to_unselected() {
+        let root = std::env::temp_dir().join("test_plan_account_switch_to_unselected");
+        let _ = std::fs::remove_dir_all(&root);
+        let riot_data = root.join("Riot Client").join("Data");
+        let account_data = root.join("accounts");
+        std::fs::create_dir_all(&riot_data).unwrap();
+
+        let settings = settings_with_paths(&riot_data, &account_data);
+        let (target, steps) = plan_account_switch(&settings, None).unwrap();
+
+        let unselected = account_data.join("_unselected");
+        assert_eq!(target, unselected);
+        assert_eq!(
+            steps,
+            vec![
+                SwitchStep::CreateTargetDir { path: path_string(&unselected) },
+                SwitchStep::MoveContents {
+                    from: path_string(&riot_data),
+                    to: path_string(&unselected),
+                },
+                SwitchStep::RemoveDir { path: path_string(&riot_data) },
+                SwitchStep::ForceRemove { path: path_string(&riot_data) },
+                SwitchStep::CreateJunction {
+                    link: path_string(&riot_data),
+                    target: path_string(&unselected),
+                },
+                SwitchStep::SetActiveAccount { account_id: None, previous_account_id: Some(3) },
+            ]
+        );
+
+        // Planning touches nothing on disk.
+        assert!(riot_data.is_dir());
+        assert!(!unselected.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    const SAMPLE_YAML: &str = "riot-login:
+    persist:
+        session:
+            cookies:
+                -   domain: \"auth.riotgames.com\"
+                    hostOnly: true
+                    name: \"ssid\"
+                    path: \"/\"
+                    value: \"old_ssid\"
+rso-authenticator:
+    tdid:
+        domain: \"riotgames.com\"
+        value: \"old_tdid\"
+";
+
+    fn cookies_with_ssid(ssid: &str) -> shop::RiotCookies {
+        shop::RiotCookies {
+            asid: None,
+            ccid: None,
+            clid: None,
+            sub: None,
+            csid: None,
+            ssid: Some(ssid.to_string()),
+            tdid: Some("new_tdid".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_update_yaml_file_content_lf() {
+        let updated = update_yaml_file_content(SAMPLE_YAML, &cookies_with_ssid("new_ssid"));
+        assert!(updated.contains("value: \"new_ssid\"\n"));
+        assert!(updated.contains("value: \"new_tdid\"\n"));
+        assert!(!updated.contains('\r'));
+    }
+
+    #[test]
+    fn test_update_yaml_file_content_preserves_crlf() {
+        let crlf = SAMPLE_YAML.replace('\n', "\r\n");
+        let updated = update_yaml_file_content(&crlf, &cookies_with_ssid("new_ssid"));
+        assert!(updated.contains("value: \"new_ssid\"\r\n"));
+        assert!(updated.contains("value: \"new_tdid\"\r\n"));
+        assert_eq!(updated.matches('\n').count(), updated.matches("\r\n").count());
+    }
+
+    #[test]
+    fn test_update_yaml_file_content_preserves_bom() {
+        let with_bom = format!("{}{}", UTF8_BOM, SAMPLE_YAML.replace('\n', "\r\n"));
+        let updated = update_yaml_file_content(&with_bom, &cookies_with_ssid("new_ssid"));
+        assert!(updated.starts_with(UTF8_BOM));
+        assert!(!updated[UTF8_BOM.len()..].starts_with(UTF8_BOM));
+        assert!(updated.contains("value: \"new_ssid\"\r\n"));
+    }
+
+    #[test]
+    fn test_update_yaml_file_content_unchanged() {
+        let crlf = format!("{}{}", UTF8_BOM, SAMPLE_YAML.replace('\n', "\r\n"));
+        let mut cookies = cookies_with_ssid("old_ssid");
+        cookies.tdid = Some("old_tdid".to_string());
+        assert_eq!(update_yaml_file_content(&crlf, &cookies), crlf);
+    }
+
+    #[test]
+    fn test_cookies_need_write() {
+        let current = cookies_with_ssid("old");
+
+        assert!(!cookies_need_write(&current, &current.clone()));
+        assert!(cookies_need_write(&current, &cookies_with_ssid("new")));
+
+        let mut nothing = cookies_with_ssid("old");
+        nothing.ssid = None;
+        assert!(!cookies_need_write(&current, &nothing));
+    }
+
+    #[test]
+    fn test_parse_cli_args() {
+        let args = ["--switch", "3", "--launch"].map(String::from);
+        assert_eq!(
+            parse_cli_args(args).unwrap(),
+            CliOptions { switch_to: Some(3), launch: true, exit: false }
+        );
+
+        assert_eq!(parse_cli_args(Vec::new()).unwrap(), CliOptions::default());
+        assert!(parse_cli_args(["--switch".to_string()]).is_err());
+        assert!(parse_cli_args(["--switch", "main"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn test_switch_guard_rejects_concurrent_switch() {
+        let (held_tx, held_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        let first = std::thread::spawn(move || {
+            let _guard = SwitchGuard::acquire().unwrap();
+            held_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        });
+
+        held_rx.recv().unwrap();
+        let second = std::thread::spawn(SwitchGuard::acquire).join().unwrap();
+        assert_eq!(second.err().as_deref(), Some("Account switch already in progress"));
+
+        release_tx.send(()).unwrap();
+        first.join().unwrap();
+        assert!(SwitchGuard::acquire().is_ok());
+    }
+
+    #[test]
+    fn test_cookies_from_yaml_reads_session_and_tdid() {
+        let doc = parse_yaml_document(SAMPLE_YAML).unwrap();
+        let cookies = cookies_from_yaml(&doc);
+        assert_eq!(cookies.ssid.as_deref(), Some("old_ssid"));
+        assert_eq!(cookies.tdid.as_deref(), Some("old_tdid"));
+        assert!(cookies.sub.is_none());
+    }
+
+    #[test]
+    fn test_find_yaml_in_data_dir_direct() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let yaml = dir.path().join("RiotGamesPrivateSettings.yaml");
+        std::fs::write(&yaml, SAMPLE_YAML).unwrap();
+
+        assert_eq!(find_yaml_in_data_dir(dir.path()), Some(yaml));
+    }
+
+    #[test]
+    fn test_find_yaml_in_data_dir_config_subdir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("Config")).unwrap();
+        let yaml = dir.path().join("Config").join("RiotGamesPrivateSettings.yaml");
+        std::fs::write(&yaml, SAMPLE_YAML).unwrap();
+
+        assert_eq!(find_yaml_in_data_dir(dir.path()), Some(yaml));
+    }
+
+    #[test]
+    fn test_find_yaml_in_data_dir_missing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(find_yaml_in_data_dir(dir.path()), None);
+    }
+
+    #[test]
+    fn test_find_yaml_leftovers() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("Config")).unwrap();
+        let yaml = dir.path().join("RiotGamesPrivateSettings.yaml");
+        let tmp = dir.path().join("RiotGamesPrivateSettings.yaml.tmp");
+        let backup = dir
+            .path()
+            .join("Config")
+            .join("RiotGamesPrivateSettings.yaml.bak-1700000000");
+        let unrelated = dir.path().join("other.yaml.tmp");
+        for path in [&yaml, &tmp, &backup, &unrelated] {
+            std::fs::write(path, "x").unwrap();
+        }
+
+        let mut leftovers = find_yaml_leftovers(dir.path());
+        leftovers.sort();
+        let mut expected = vec![tmp, backup];
+        expected.sort();
+        assert_eq!(leftovers, expected);
+    }
+
+    #[test]
+    fn test_parse_yaml_document_with_bom() {
+        let with_bom = format!("{}{}", UTF8_BOM, SAMPLE_YAML);
+        let doc = parse_yaml_document(&with_bom).unwrap();
+        assert!(doc.get("riot-login").is_some());
+    }
+}
@@ -3,12 +3,81 @@ use std::os::windows::process::CommandExt;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
 use std::time::Duration;
+use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 use wmi::{COMLibrary, Variant, WMIConnection};
 
 static RIOT_CLIENT_RUNNING: OnceLock<AtomicBool> = OnceLock::new();
 static VALORANT_RUNNING: OnceLock<AtomicBool> = OnceLock::new();
 
+const RIOT_CLIENT_PROCESS: &str = "RiotClientServices.exe";
+const VALORANT_PROCESS: &str = "VALORANT-Win64-Shipping.exe";
+const DEFAULT_POLL_SECS: u64 = 2;
+
+/// How often the process monitor polls and which extra executables it watches.
+///
+/// Read from settings once, when the monitor starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessMonitorConfig {
+    pub poll_secs: u64,
+    pub extra_processes: Vec<String>,
+}
+
+impl Default for ProcessMonitorConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_POLL_SECS, Vec::new())
+    }
+}
+
+impl ProcessMonitorConfig {
+    /// `poll_secs` is raised to at least 1; Riot Client and Valorant are always
+    /// watched, so they are dropped from `extra_processes` along with duplicates.
+    pub fn new(poll_secs: u64, extra_processes: Vec<String>) -> Self {
+        let mut extra: Vec<String> = Vec::with_capacity(extra_processes.len());
+        for name in extra_processes {
+            let duplicate = [RIOT_CLIENT_PROCESS, VALORANT_PROCESS]
+                .into_iter()
+                .chain(extra.iter().map(String::as_str))
+                .any(|existing| existing.eq_ignore_ascii_case(&name));
+            if !duplicate {
+                extra.push(name);
+            }
+        }
+
+        Self {
+            poll_secs: poll_secs.max(1),
+            extra_processes: extra,
+        }
+    }
+
+    pub fn from_settings(settings: &crate::db::Settings) -> Self {
+        Self::new(
+            u64::from(settings.process_poll_secs),
+            settings.extra_watched_processes.clone(),
+        )
+    }
+}
+
+/// Payload of the `process-status` event, emitted for every watched process.
+#[derive(Clone, Serialize)]
+struct ProcessStatusEvent<'a> {
+    name: &'a str,
+    running: bool,
+}
+
+fn emit_process_status(app_handle: &AppHandle, name: &str, running: bool) {
+    if let Err(e) = app_handle.emit("process-status", ProcessStatusEvent { name, running }) {
+        eprintln!("Failed to emit process-status: {}", e);
+    }
+}
+
+/// Running state of the processes the monitor tracks.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessStatus {
+    pub riot_client_running: bool,
+    pub valorant_running: bool,
+}
+
 fn query_process_running(wmi_con: &WMIConnection, process_name: &str) -> bool {
     let query = format!(
         "SELECT Name FROM Win32_Process WHERE Name = '{}'",
@@ -36,14 +105,19 @@ pub fn check_riot_client_running() -> bool {
     RIOT_CLIENT_RUNNING
         .get()
         .map(|a| a.load(Ordering::Relaxed))
-        .unwrap_or_else(|| check_process_running("RiotClientServices.exe"))
+        .unwrap_or_else(|| check_process_running(RIOT_CLIENT_PROCESS))
 }
 
 pub fn check_valorant_running() -> bool {
     VALORANT_RUNNING
         .get()
         .map(|a| a.load(Ordering::Relaxed))
-        .unwrap_or_else(|| check_process_running("VALORANT-Win64-Shipping.exe"))
+        .unwrap_or_else(|| check_process_running(VALORANT_PROCESS))
+}
+
+/// Riot Vanguard's user-mode service; a running instance can hold Riot data files open.
+pub fn check_vanguard_running() -> bool {
+    check_process_running("vgc.exe")
 }
 
 pub fn kill_riot_client() -> Result<(), String> {
@@ -61,7 +135,103 @@ pub fn kill_riot_client() -> Result<(), String> {
     }
 }
 
+/// Split a stored launch argument string into argv, honouring double-quoted segments.
+///
+/// Whitespace separates arguments except inside `"..."`; the quotes themselves
+/// are dropped, so `--locale="ja_JP"` becomes `--locale=ja_JP`. An unterminated
+/// quote is an error. Arguments known to be safe with RiotClientServices.exe:
+///
+/// * `--launch-product=valorant` and `--launch-patchline=live` (or `pbe`)
+/// * `--locale=<code>`, e.g. `--locale=ja_JP`
+/// * `--allow-multiple-clients`
+pub fn parse_launch_args(raw: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+
+    for c in raw.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err("Unterminated quote in launch arguments".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Launch arguments of the active account, or none when there is no active account.
+fn active_account_launch_args() -> Result<Vec<String>, String> {
+    let settings = crate::db::get_settings()?;
+    let Some(account_id) = settings.active_account_id else {
+        return Ok(Vec::new());
+    };
+    match crate::db::get_account(account_id)?.launch_args {
+        Some(raw) => parse_launch_args(&raw),
+        None => Ok(Vec::new()),
+    }
+}
+
 pub fn launch_riot_client() -> Result<(), String> {
+    let launch_args = active_account_launch_args()?;
+    if !launch_args.is_empty() {
+        log::info!("Launching Riot Client with account arguments: {:?}", launch_args);
+    }
+
+    spawn_riot_client(&launch_args)
+}
+
+/// Arguments that make the Riot Client start VALORANT on the live patchline.
+const VALORANT_LAUNCH_ARGS: [&str; 2] = ["--launch-product=valorant", "--launch-patchline=live"];
+
+/// `VALORANT_LAUNCH_ARGS` followed by the account's own arguments.
+///
+/// Account arguments that pick a product or patchline replace the defaults, so
+/// an account configured for `pbe` still launches there.
+fn valorant_launch_args(account_args: Vec<String>) -> Vec<String> {
+    let overrides = |prefix: &str| account_args.iter().any(|a| a.starts_with(prefix));
+
+    let mut args: Vec<String> = VALORANT_LAUNCH_ARGS
+        .iter()
+        .filter(|default| !overrides(default.split('=').next().unwrap_or_default()))
+        .map(|a| a.to_string())
+        .collect();
+    args.extend(account_args);
+    args
+}
+
+/// Start VALORANT directly through the Riot Client instead of stopping at its Play screen.
+pub fn launch_valorant() -> Result<(), String> {
+    if check_valorant_running() {
+        return Err("VALORANT is already running".to_string());
+    }
+
+    let launch_args = valorant_launch_args(active_account_launch_args()?);
+    log::info!("Launching VALORANT with arguments: {:?}", launch_args);
+
+    spawn_riot_client(&launch_args)
+}
+
+/// Spawn the first existing Riot Client executable with `launch_args`.
+fn spawn_riot_client(launch_args: &[String]) -> Result<(), String> {
     use crate::db::get_settings;
 
     // Try to get path from settings first
@@ -85,6 +255,7 @@ pub fn launch_riot_client() -> Result<(), String> {
     for path in &candidates {
         if std::path::Path::new(path).exists() {
             std::process::Command::new(path)
+                .args(launch_args)
                 .creation_flags(0x08000000)
                 .spawn()
                 .map_err(|e| e.to_string())?;
@@ -95,11 +266,83 @@ pub fn launch_riot_client() -> Result<(), String> {
     Err("Riot Client executable not found".to_string())
 }
 
-pub fn start_process_monitor(app_handle: AppHandle) {
+/// Result of checking a candidate Riot Client service path.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiotClientPathStatus {
+    /// The path points to an existing `.exe` file.
+    pub valid: bool,
+    /// The file is named `RiotClientServices.exe`; other names still launch but are likely wrong.
+    pub is_riot_client_services: bool,
+    pub error: Option<String>,
+}
+
+/// Check that `path` is an existing `.exe` file, and whether it is named like the Riot Client.
+pub fn validate_riot_client_path(path: &std::path::Path) -> RiotClientPathStatus {
+    let is_riot_client_services = path
+        .file_name()
+        .map(|n| n.to_string_lossy().eq_ignore_ascii_case(RIOT_CLIENT_PROCESS))
+        .unwrap_or(false);
+    let invalid = |error: String| RiotClientPathStatus {
+        valid: false,
+        is_riot_client_services,
+        error: Some(error),
+    };
+
+    let is_exe = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("exe"))
+        .unwrap_or(false);
+    if !is_exe {
+        return invalid("Path is not an .exe file".to_string());
+    }
+
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.is_file() => RiotClientPathStatus {
+            valid: true,
+            is_riot_client_services,
+            error: None,
+        },
+        Ok(_) => invalid("Path is a directory".to_string()),
+        Err(e) => invalid(format!("Cannot access file: {}", e)),
+    }
+}
+
+/// Store `now` in `flag` and return the previous value (`now` if uninitialized).
+fn swap_status(flag: &OnceLock<AtomicBool>, now: bool) -> bool {
+    flag.get_or_init(|| AtomicBool::new(now))
+        .swap(now, Ordering::Relaxed)
+}
+
+/// Query both processes immediately, update the monitor flags, and emit
+/// `riot-client-status` / `valorant-status` regardless of whether they changed.
+pub fn refresh_process_status(app_handle: &AppHandle) -> ProcessStatus {
+    let status = ProcessStatus {
+        riot_client_running: check_process_running(RIOT_CLIENT_PROCESS),
+        valorant_running: check_process_running(VALORANT_PROCESS),
+    };
+
+    swap_status(&RIOT_CLIENT_RUNNING, status.riot_client_running);
+    swap_status(&VALORANT_RUNNING, status.valorant_running);
+
+    if let Err(e) = app_handle.emit("riot-client-status", status.riot_client_running) {
+        eprintln!("Failed to emit riot-client-status: {}", e);
+    }
+    if let Err(e) = app_handle.emit("valorant-status", status.valorant_running) {
+        eprintln!("Failed to emit valorant-status: {}", e);
+    }
+
+    status
+}
+
+/// Poll the watched processes every `config.poll_secs` on a background thread.
+///
+/// Riot Client and Valorant changes emit `riot-client-status` / `valorant-status`;
+/// every watched process, extras included, also emits `process-status` on change.
+pub fn start_process_monitor(app_handle: AppHandle, config: ProcessMonitorConfig) {
     RIOT_CLIENT_RUNNING
-        .get_or_init(|| AtomicBool::new(check_process_running("RiotClientServices.exe")));
+        .get_or_init(|| AtomicBool::new(check_process_running(RIOT_CLIENT_PROCESS)));
     VALORANT_RUNNING
-        .get_or_init(|| AtomicBool::new(check_process_running("VALORANT-Win64-Shipping.exe")));
+        .get_or_init(|| AtomicBool::new(check_process_running(VALORANT_PROCESS)));
 
     std::thread::spawn(move || {
         let com_lib = match COMLibrary::new() {
@@ -117,29 +360,35 @@ pub fn start_process_monitor(app_handle: AppHandle) {
             }
         };
 
+        // Extras have no initial state, so their first poll always emits
+        let mut extra_running: HashMap<String, bool> = HashMap::new();
+
         loop {
-            std::thread::sleep(Duration::from_secs(2));
+            std::thread::sleep(Duration::from_secs(config.poll_secs));
 
-            let riot_now = query_process_running(&wmi_con, "RiotClientServices.exe");
-            let riot_prev = RIOT_CLIENT_RUNNING
-                .get()
-                .unwrap()
-                .swap(riot_now, Ordering::Relaxed);
+            let riot_now = query_process_running(&wmi_con, RIOT_CLIENT_PROCESS);
+            let riot_prev = swap_status(&RIOT_CLIENT_RUNNING, riot_now);
             if riot_now != riot_prev {
                 if let Err(e) = app_handle.emit("riot-client-status", riot_now) {
                     eprintln!("Failed to emit riot-client-status: {}", e);
                 }
+                emit_process_status(&app_handle, RIOT_CLIENT_PROCESS, riot_now);
             }
 
-            let valo_now = query_process_running(&wmi_con, "VALORANT-Win64-Shipping.exe");
-            let valo_prev = VALORANT_RUNNING
-                .get()
-                .unwrap()
-                .swap(valo_now, Ordering::Relaxed);
+            let valo_now = query_process_running(&wmi_con, VALORANT_PROCESS);
+            let valo_prev = swap_status(&VALORANT_RUNNING, valo_now);
             if valo_now != valo_prev {
                 if let Err(e) = app_handle.emit("valorant-status", valo_now) {
                     eprintln!("Failed to emit valorant-status: {}", e);
                 }
+                emit_process_status(&app_handle, VALORANT_PROCESS, valo_now);
+            }
+
+            for name in &config.extra_processes {
+                let now = query_process_running(&wmi_con, name);
+                if extra_running.insert(name.clone(), now) != Some(now) {
+                    emit_process_status(&app_handle, name, now);
+                }
             }
         }
     });
@@ -178,6 +427,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_monitor_config() {
+        let config = ProcessMonitorConfig::new(
+            0,
+            vec![
+                "VALORANT.exe".to_string(),
+                "riotclientservices.exe".to_string(),
+                "valorant.EXE".to_string(),
+            ],
+        );
+        assert_eq!(config.poll_secs, 1);
+        assert_eq!(config.extra_processes, vec!["VALORANT.exe"]);
+        assert_eq!(ProcessMonitorConfig::default().poll_secs, DEFAULT_POLL_SECS);
+    }
+
+    #[test]
+    fn test_valorant_launch_args() {
+        assert_eq!(
+            valorant_launch_args(vec!["--locale=ja_JP".to_string()]),
+            vec!["--launch-product=valorant", "--launch-patchline=live", "--locale=ja_JP"]
+        );
+        assert_eq!(
+            valorant_launch_args(vec!["--launch-patchline=pbe".to_string()]),
+            vec!["--launch-product=valorant", "--launch-patchline=pbe"]
+        );
+    }
+
+    #[test]
+    fn test_parse_launch_args() {
+        assert_eq!(parse_launch_args("").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            parse_launch_args("  --launch-product=valorant   --locale=\"ja_JP\" ").unwrap(),
+            vec!["--launch-product=valorant", "--locale=ja_JP"]
+        );
+        assert_eq!(
+            parse_launch_args(r#"--name "two words" """#).unwrap(),
+            vec!["--name", "two words", ""]
+        );
+        assert!(parse_launch_args(r#"--locale="ja_JP"#).is_err());
+    }
+
+    #[test]
+    fn test_validate_riot_client_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("RiotClientServices.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+        let other = dir.path().join("notepad.exe");
+        std::fs::write(&other, b"MZ").unwrap();
+
+        let status = validate_riot_client_path(&exe);
+        assert!(status.valid && status.is_riot_client_services);
+
+        let status = validate_riot_client_path(&other);
+        assert!(status.valid && !status.is_riot_client_services);
+
+        assert!(!validate_riot_client_path(&dir.path().join("missing.exe")).valid);
+        assert!(!validate_riot_client_path(&dir.path().join("RiotClientServices.txt")).valid);
+    }
+
     #[test]
     fn test_check_riot_client_running_does_not_panic() {
         let _ = check_riot_client_running();
@@ -0,0 +1,64 @@
+use serde::Deserialize;
+
+/// ItemTypeID of agents in the store entitlements API.
+pub(super) const ITEM_TYPE_AGENT: &str = "01bb38e1-da47-4e6a-9b3d-945fe4e7c3d7";
+
+// -- Internal API response types ----------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct ApiItemEntitlements {
+    #[serde(rename = "Entitlements", default)]
+    entitlements: Vec<ApiEntitlement>,
+}
+
+#[derive(Deserialize)]
+struct ApiEntitlement {
+    #[serde(rename = "ItemID")]
+    item_id: String,
+}
+
+/// Extract the owned agent UUIDs from an entitlements response, deduplicated in order.
+///
+/// Free starter agents are not always listed, so callers merge in the base roster.
+pub(super) fn parse_owned_agents(raw: ApiItemEntitlements) -> Vec<String> {
+    let mut uuids: Vec<String> = Vec::with_capacity(raw.entitlements.len());
+    for entitlement in raw.entitlements {
+        let uuid = entitlement.item_id.to_lowercase();
+        if !uuid.is_empty() && !uuids.contains(&uuid) {
+            uuids.push(uuid);
+        }
+    }
+    uuids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_owned_agents() {
+        let json = r#"{
+            "ItemTypeID": "01bb38e1-da47-4e6a-9b3d-945fe4e7c3d7",
+            "Entitlements": [
+                { "TypeID": "type", "ItemID": "E370FA57-4757-3604-3648-499E1F642D3F" },
+                { "TypeID": "type", "ItemID": "e370fa57-4757-3604-3648-499e1f642d3f" },
+                { "TypeID": "type", "ItemID": "a3bfb853-43b2-7238-a4f1-ad90e9e46bcc" }
+            ]
+        }"#;
+
+        let raw: ApiItemEntitlements = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parse_owned_agents(raw),
+            vec![
+                "e370fa57-4757-3604-3648-499e1f642d3f".to_string(),
+                "a3bfb853-43b2-7238-a4f1-ad90e9e46bcc".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_owned_agents_missing_list() {
+        let raw: ApiItemEntitlements = serde_json::from_str(r#"{"ItemTypeID": "x"}"#).unwrap();
+        assert!(parse_owned_agents(raw).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Serialize;
+
+use crate::db;
+
+use super::types::Storefront;
+
+/// Payload of the `bundle-discount-alert` event.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BundleDiscountAlert {
+    pub account_id: i64,
+    pub bundle_name: String,
+    pub discount_percent: f64,
+}
+
+/// Bundles in a freshly fetched storefront whose discount meets `threshold` (0–100)
+/// and that have not been alerted on since their current offer started.
+///
+/// Alerted bundles are kept in `alerted_bundles` until their offer ends, so a
+/// restart does not repeat them. Featured bundles are shared by every account,
+/// so one alert covers them all.
+pub fn bundle_discount_alerts(
+    account_id: i64,
+    storefront: &Storefront,
+    threshold: f64,
+    now: i64,
+) -> Vec<BundleDiscountAlert> {
+    let result = db::init::get_connection(None)
+        .and_then(|conn| select_alerts_with(&conn, account_id, storefront, threshold, now));
+    match result {
+        Ok(alerts) => alerts,
+        Err(e) => {
+            log::warn!("Bundle alerts: failed to check alerted bundles: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn select_alerts_with(
+    conn: &rusqlite::Connection,
+    account_id: i64,
+    storefront: &Storefront,
+    threshold: f64,
+    now: i64,
+) -> Result<Vec<BundleDiscountAlert>, String> {
+    conn.execute("DELETE FROM alerted_bundles WHERE expires_at <= ?1", [now])
+        .map_err(|e| e.to_string())?;
+
+    let mut alerts = Vec::new();
+    for bundle in storefront.bundles.iter().flatten() {
+        if bundle.total_discount_percent < threshold {
+            continue;
+        }
+
+        // One statement, so two fetches finishing together cannot both alert
+        let inserted = conn
+            .execute(
+                "INSERT INTO alerted_bundles (bundle_name, expires_at)
+                 SELECT ?1, ?2
+                  WHERE NOT EXISTS (SELECT 1 FROM alerted_bundles WHERE bundle_name = ?1)",
+                rusqlite::params![bundle.name, now + bundle.bundle_remaining_secs as i64],
+            )
+            .map_err(|e| e.to_string())?;
+        if inserted == 0 {
+            continue;
+        }
+
+        alerts.push(BundleDiscountAlert {
+            account_id,
+            bundle_name: bundle.name.clone(),
+            discount_percent: bundle.total_discount_percent,
+        });
+    }
+    Ok(alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shop::types::Bundle;
+
+    fn storefront_with_bundle(name: &str, discount_percent: f64, remaining_secs: u64) -> Storefront {
+        Storefront {
+            daily_offers: vec![],
+            daily_remaining_secs: 0,
+            bundles: Some(vec![Bundle {
+                name: name.to_string(),
+                total_base_cost: 10000,
+                total_discounted_cost: 6000,
+                total_discount_percent: discount_percent,
+                bundle_remaining_secs: remaining_secs,
+                items: vec![],
+            }]),
+            night_market: None,
+            night_market_remaining_secs: None,
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_select_alerts_threshold_and_dedup() {
+        let db_path = std::env::temp_dir().join("test_select_alerts_threshold_and_dedup.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = db::init::initialize_database(Some(db_path.clone())).unwrap();
+            let deal = storefront_with_bundle("Spectrum", 40.0, 3600);
+
+            let small = storefront_with_bundle("Ion", 20.0, 3600);
+            assert!(select_alerts_with(&conn, 1, &small, 35.0, 0).unwrap().is_empty());
+
+            let alerts = select_alerts_with(&conn, 1, &deal, 35.0, 0).unwrap();
+            assert_eq!(alerts.len(), 1);
+            assert_eq!(alerts[0].bundle_name, "Spectrum");
+
+            // Same bundle seen from another account before it expires: no repeat.
+            assert!(select_alerts_with(&conn, 2, &deal, 35.0, 100).unwrap().is_empty());
+
+            // The next time the bundle is offered it alerts again.
+            assert_eq!(select_alerts_with(&conn, 2, &deal, 35.0, 3600).unwrap().len(), 1);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::db;
-use super::types::{Bundle, BundleItem, DailyOffer, NightMarketOffer, Storefront};
+use super::loadout::Loadout;
+use super::reset::secs_until_daily_reset;
+use super::types::{
+    AccessoryOffer, Bundle, BundleItem, CachedShopInfo, DailyOffer, NightMarketOffer, Storefront,
+};
 
 /// Internal representation used for bundle cache serialization.
 ///
@@ -17,6 +21,37 @@ struct CachedBundle {
     items: Vec<BundleItem>,
 }
 
+/// Raw `storefront_cache` columns, in `SELECT` order.
+type CacheRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    i64,
+    Option<i64>,
+    Option<String>,
+    Option<i64>,
+);
+
+/// Longest daily shop duration taken at face value from the API (two days).
+const MAX_DAILY_REMAINING_SECS: u64 = 172_800;
+
+/// How long the daily offers of a storefront fetched at `now` stay cached.
+///
+/// Riot's reported duration is kept when plausible. A zero (seen on some v1
+/// responses) or an over-large value falls back to the next UTC midnight, so
+/// the cache neither expires instantly nor outlives the rotation.
+pub(super) fn daily_cache_ttl_secs(reported_secs: u64, now: i64) -> u64 {
+    if reported_secs == 0 || reported_secs > MAX_DAILY_REMAINING_SECS {
+        log::warn!(
+            "Cache: implausible daily_remaining_secs {}, expiring at the next daily reset",
+            reported_secs
+        );
+        secs_until_daily_reset(now as u64)
+    } else {
+        reported_secs
+    }
+}
+
 fn current_unix_secs() -> i64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -37,17 +72,36 @@ pub fn load_cached_storefront(account_id: i64) -> Option<Storefront> {
         }
     };
 
-    let row: Option<(String, Option<String>, Option<String>, i64, Option<i64>)> = conn
+    let row: Option<CacheRow> = conn
         .query_row(
-            "SELECT daily_offers_json, night_market_json, bundles_json, expires_at, nm_expires_at
+            "SELECT daily_offers_json, night_market_json, bundles_json, expires_at, nm_expires_at,
+                    accessory_store_json, accessory_expires_at
                FROM storefront_cache
               WHERE account_id = ?1",
             [account_id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            },
         )
         .ok();
 
-    let (daily_json, night_json, bundles_json, expires_at, nm_expires_at) = match row {
+    let (
+        daily_json,
+        night_json,
+        bundles_json,
+        expires_at,
+        nm_expires_at,
+        accessory_json,
+        accessory_expires_at,
+    ) = match row {
         Some(r) => r,
         None => {
             log::info!("Cache: miss (no entry) for account {}", account_id);
@@ -86,6 +140,21 @@ pub fn load_cached_storefront(account_id: i64) -> Option<Storefront> {
         .filter(|&ea| ea > now)
         .map(|ea| (ea - now) as u64);
 
+    let accessory_store: Option<Vec<AccessoryOffer>> = match accessory_json {
+        Some(ref json) => match serde_json::from_str(json) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log::warn!("Cache: failed to deserialize accessory_store: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let accessory_remaining_secs = accessory_expires_at
+        .filter(|&ea| ea > now)
+        .map(|ea| (ea - now) as u64);
+
     let bundles: Option<Vec<Bundle>> = match bundles_json {
         Some(ref json) => match serde_json::from_str::<Vec<CachedBundle>>(json) {
             Ok(cached_bundles) => {
@@ -124,6 +193,10 @@ pub fn load_cached_storefront(account_id: i64) -> Option<Storefront> {
         bundles,
         night_market,
         night_market_remaining_secs,
+        daily_expires_at: expires_at,
+        night_market_expires_at: nm_expires_at,
+        accessory_store,
+        accessory_remaining_secs,
     })
 }
 
@@ -153,11 +226,20 @@ pub fn save_storefront_cache(account_id: i64, storefront: &Storefront) {
             .ok()
     });
 
+    let accessory_json: Option<String> = storefront.accessory_store.as_ref().and_then(|offers| {
+        serde_json::to_string(offers)
+            .map_err(|e| log::warn!("Cache: failed to serialize accessory_store: {}", e))
+            .ok()
+    });
+
     let now = current_unix_secs();
-    let expires_at = now + storefront.daily_remaining_secs as i64;
+    let expires_at = now + daily_cache_ttl_secs(storefront.daily_remaining_secs, now) as i64;
     let nm_expires_at: Option<i64> = storefront
         .night_market_remaining_secs
         .map(|secs| now + secs as i64);
+    let accessory_expires_at: Option<i64> = storefront
+        .accessory_remaining_secs
+        .map(|secs| now + secs as i64);
 
     let bundles_json: Option<String> = storefront.bundles.as_ref().and_then(|bundles| {
         let cached: Vec<CachedBundle> = bundles
@@ -178,16 +260,28 @@ pub fn save_storefront_cache(account_id: i64, storefront: &Storefront) {
 
     let result = conn.execute(
         "INSERT INTO storefront_cache
-             (account_id, daily_offers_json, night_market_json, bundles_json, expires_at, nm_expires_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             (account_id, daily_offers_json, night_market_json, bundles_json, expires_at, nm_expires_at,
+              accessory_store_json, accessory_expires_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
          ON CONFLICT(account_id) DO UPDATE SET
              daily_offers_json = excluded.daily_offers_json,
              night_market_json = excluded.night_market_json,
              bundles_json = excluded.bundles_json,
              expires_at = excluded.expires_at,
              nm_expires_at = excluded.nm_expires_at,
+             accessory_store_json = excluded.accessory_store_json,
+             accessory_expires_at = excluded.accessory_expires_at,
              cached_at = CURRENT_TIMESTAMP",
-        rusqlite::params![account_id, daily_json, night_json, bundles_json, expires_at, nm_expires_at],
+        rusqlite::params![
+            account_id,
+            daily_json,
+            night_json,
+            bundles_json,
+            expires_at,
+            nm_expires_at,
+            accessory_json,
+            accessory_expires_at
+        ],
     );
 
     match result {
@@ -195,3 +289,226 @@ pub fn save_storefront_cache(account_id: i64, storefront: &Storefront) {
         Err(e) => log::warn!("Cache: failed to save for account {}: {}", account_id, e),
     }
 }
+
+/// List every cached storefront with its expiry timestamps.
+///
+/// Only reads the expiry columns, so no storefront JSON is deserialized.
+pub fn list_cached_storefronts() -> Result<Vec<CachedShopInfo>, String> {
+    let conn = db::init::get_connection(None)?;
+    let now = current_unix_secs();
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT account_id, expires_at, nm_expires_at
+               FROM storefront_cache
+              ORDER BY account_id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries = stmt
+        .query_map([], |row| {
+            let expires_at: i64 = row.get(1)?;
+            Ok(CachedShopInfo {
+                account_id: row.get(0)?,
+                expires_at,
+                nm_expires_at: row.get(2)?,
+                is_valid: expires_at > now,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries)
+}
+
+/// Delete the cached storefront of one account, or of every account when `account_id` is `None`.
+///
+/// Returns the number of rows removed.
+pub fn clear_storefront_cache(account_id: Option<i64>) -> Result<usize, String> {
+    let conn = db::init::get_connection(None)?;
+    clear_storefront_cache_with(&conn, account_id)
+}
+
+fn clear_storefront_cache_with(
+    conn: &rusqlite::Connection,
+    account_id: Option<i64>,
+) -> Result<usize, String> {
+    let removed = match account_id {
+        Some(id) => conn.execute("DELETE FROM storefront_cache WHERE account_id = ?1", [id]),
+        None => conn.execute("DELETE FROM storefront_cache", []),
+    }
+    .map_err(|e| e.to_string())?;
+
+    log::info!("Cache: cleared {} storefront(s) (account={:?})", removed, account_id);
+    Ok(removed)
+}
+
+/// How long a cached loadout is served before refetching.
+const LOADOUT_CACHE_TTL_SECS: i64 = 10 * 60;
+
+/// Load the account's cached loadout if it is younger than `LOADOUT_CACHE_TTL_SECS`.
+pub fn load_cached_loadout(account_id: i64) -> Option<Loadout> {
+    let conn = db::init::get_connection(None)
+        .map_err(|e| log::warn!("Cache: failed to open db: {}", e))
+        .ok()?;
+
+    let (json, fetched_at): (String, i64) = conn
+        .query_row(
+            "SELECT loadout_json, fetched_at FROM loadout_cache WHERE account_id = ?1",
+            [account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok()?;
+
+    if current_unix_secs() - fetched_at >= LOADOUT_CACHE_TTL_SECS {
+        log::info!("Cache: loadout expired for account {}", account_id);
+        return None;
+    }
+
+    serde_json::from_str(&json)
+        .map_err(|e| log::warn!("Cache: failed to deserialize loadout: {}", e))
+        .ok()
+}
+
+/// Persist a freshly fetched loadout. Errors are logged, never propagated.
+pub fn save_loadout_cache(account_id: i64, loadout: &Loadout) {
+    let conn = match db::init::get_connection(None) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Cache: failed to open db for save: {}", e);
+            return;
+        }
+    };
+
+    let json = match serde_json::to_string(loadout) {
+        Ok(j) => j,
+        Err(e) => {
+            log::warn!("Cache: failed to serialize loadout: {}", e);
+            return;
+        }
+    };
+
+    let result = conn.execute(
+        "INSERT INTO loadout_cache (account_id, loadout_json, fetched_at)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(account_id) DO UPDATE SET
+             loadout_json = excluded.loadout_json,
+             fetched_at = excluded.fetched_at",
+        rusqlite::params![account_id, json, current_unix_secs()],
+    );
+
+    if let Err(e) = result {
+        log::warn!("Cache: failed to save loadout for account {}: {}", account_id, e);
+    }
+}
+
+/// How long cached agent entitlements are served before refetching.
+const OWNED_AGENTS_CACHE_TTL_SECS: i64 = 60 * 60;
+
+/// Load the account's cached owned-agent UUIDs if younger than `OWNED_AGENTS_CACHE_TTL_SECS`.
+pub fn load_cached_owned_agents(account_id: i64) -> Option<Vec<String>> {
+    let conn = db::init::get_connection(None)
+        .map_err(|e| log::warn!("Cache: failed to open db: {}", e))
+        .ok()?;
+
+    let (json, fetched_at): (String, i64) = conn
+        .query_row(
+            "SELECT agents_json, fetched_at FROM owned_agents_cache WHERE account_id = ?1",
+            [account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok()?;
+
+    if current_unix_secs() - fetched_at >= OWNED_AGENTS_CACHE_TTL_SECS {
+        log::info!("Cache: owned agents expired for account {}", account_id);
+        return None;
+    }
+
+    serde_json::from_str(&json)
+        .map_err(|e| log::warn!("Cache: failed to deserialize owned agents: {}", e))
+        .ok()
+}
+
+/// Persist freshly fetched owned-agent UUIDs. Errors are logged, never propagated.
+pub fn save_owned_agents_cache(account_id: i64, agent_uuids: &[String]) {
+    let conn = match db::init::get_connection(None) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Cache: failed to open db for save: {}", e);
+            return;
+        }
+    };
+
+    let json = match serde_json::to_string(agent_uuids) {
+        Ok(j) => j,
+        Err(e) => {
+            log::warn!("Cache: failed to serialize owned agents: {}", e);
+            return;
+        }
+    };
+
+    let result = conn.execute(
+        "INSERT INTO owned_agents_cache (account_id, agents_json, fetched_at)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(account_id) DO UPDATE SET
+             agents_json = excluded.agents_json,
+             fetched_at = excluded.fetched_at",
+        rusqlite::params![account_id, json, current_unix_secs()],
+    );
+
+    if let Err(e) = result {
+        log::warn!("Cache: failed to save owned agents for account {}: {}", account_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_storefront_cache() {
+        let db_path = std::env::temp_dir().join("test_clear_storefront_cache.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = db::init::initialize_database(Some(db_path.clone())).unwrap();
+            for account_id in 1..=3 {
+                conn.execute(
+                    "INSERT INTO storefront_cache (account_id, daily_offers_json, expires_at)
+                     VALUES (?1, '[]', 0)",
+                    [account_id],
+                )
+                .unwrap();
+            }
+
+            assert_eq!(clear_storefront_cache_with(&conn, Some(2)).unwrap(), 1);
+            assert_eq!(clear_storefront_cache_with(&conn, Some(2)).unwrap(), 0);
+            assert_eq!(clear_storefront_cache_with(&conn, None).unwrap(), 2);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
+    // 2024-01-01 06:00:00 UTC, 18 hours before the next reset.
+    const NOW: i64 = 1_704_088_800;
+
+    #[test]
+    fn test_daily_cache_ttl_keeps_plausible_value() {
+        assert_eq!(daily_cache_ttl_secs(3600, NOW), 3600);
+        assert_eq!(daily_cache_ttl_secs(MAX_DAILY_REMAINING_SECS, NOW), MAX_DAILY_REMAINING_SECS);
+    }
+
+    #[test]
+    fn test_daily_cache_ttl_zero_uses_next_reset() {
+        assert_eq!(daily_cache_ttl_secs(0, NOW), 18 * 3600);
+    }
+
+    #[test]
+    fn test_daily_cache_ttl_over_large_uses_next_reset() {
+        assert_eq!(daily_cache_ttl_secs(MAX_DAILY_REMAINING_SECS + 1, NOW), 18 * 3600);
+        assert_eq!(daily_cache_ttl_secs(u64::MAX, NOW), 18 * 3600);
+    }
+}
@@ -5,9 +5,16 @@ use reqwest::cookie::{CookieStore, Jar};
 use reqwest::Client;
 use serde::Deserialize;
 
+use super::agents::{parse_owned_agents, ApiItemEntitlements, ITEM_TYPE_AGENT};
+use super::diagnose::{record_stage, ShopStage};
 use super::error::ShopError;
+use super::loadout::{parse_loadout, ApiLoadout, Loadout};
 use super::storefront::{extract_access_token, parse_storefront};
-use super::types::{ApiStorefront, EntitlementsResponse, RiotCookies, Storefront, UserInfoResponse};
+use super::types::{
+    ApiStorefront, ApiStorefrontV1, EntitlementsResponse, RiotCookies, RiotIdentity, Storefront,
+    UserInfoResponse,
+};
+use super::wallet::{parse_wallet, ApiWallet, Wallet};
 
 const VALORANT_API_BUNDLE_URL: &str = "https://valorant-api.com/v1/bundles/";
 
@@ -59,11 +66,38 @@ const AUTH_PARAMS: &[(&str, &str)] = &[
     ("scope", "account openid"),
 ];
 
-/// Derive the shard from the `clid` cookie value by stripping trailing digits.
+/// Derive the region from the `clid` cookie value by stripping trailing digits.
 ///
-/// Examples: "ap1" -> "ap", "na1" -> "na", "eu3" -> "eu", "kr" -> "kr"
+/// Examples: "ap1" -> "ap", "na1" -> "na", "br1" -> "br", "la2" -> "latam"
+pub fn region_from_clid(clid: &str) -> &str {
+    match clid.trim_end_matches(|c: char| c.is_ascii_digit()) {
+        "la" => "latam",
+        region => region,
+    }
+}
+
+/// Map a region to the pd shard that serves its storefront.
+///
+/// Brazil and LATAM have no shard of their own and are served from NA.
+pub(super) fn shard_for_region(region: &str) -> &str {
+    match region {
+        "br" | "latam" => "na",
+        shard => shard,
+    }
+}
+
+/// Derive the shard from the `clid` cookie value.
+///
+/// Examples: "ap1" -> "ap", "na1" -> "na", "eu3" -> "eu", "br1" -> "na", "kr" -> "kr"
 pub(super) fn shard_from_clid(clid: &str) -> &str {
-    clid.trim_end_matches(|c: char| c.is_ascii_digit())
+    shard_for_region(region_from_clid(clid))
+}
+
+/// Tokens needed to call the pd.*.a.pvp.net endpoints.
+struct AuthSession {
+    access_token: String,
+    entitlements_token: String,
+    puuid: String,
 }
 
 pub(super) struct ShopClient {
@@ -133,6 +167,11 @@ impl ShopClient {
         })
     }
 
+    /// Use the shard serving `region` instead of the one derived from `clid`.
+    pub(super) fn set_region(&mut self, region: &str) {
+        self.shard = shard_for_region(&region.to_ascii_lowercase()).to_string();
+    }
+
     async fn authenticate(&self) -> Result<String, ShopError> {
         let auth_body = serde_json::json!({
             "client_id": "play-valorant-web-prod",
@@ -191,8 +230,8 @@ impl ShopClient {
         Ok(data.entitlements_token)
     }
 
-    async fn get_puuid(&self, access_token: &str) -> Result<String, ShopError> {
-        let data: UserInfoResponse = self
+    async fn get_user_info(&self, access_token: &str) -> Result<UserInfoResponse, ShopError> {
+        let data = self
             .client
             .get(USERINFO_URL)
             .header("Authorization", format!("Bearer {}", access_token))
@@ -202,7 +241,33 @@ impl ShopClient {
             .json()
             .await?;
 
-        Ok(data.sub)
+        Ok(data)
+    }
+
+    async fn get_puuid(&self, access_token: &str) -> Result<String, ShopError> {
+        Ok(self.get_user_info(access_token).await?.sub)
+    }
+
+    /// Read the PUUID and current Riot ID of the logged-in account.
+    pub(super) async fn fetch_identity(&self) -> Result<RiotIdentity, ShopError> {
+        let access_token = self.authenticate().await?;
+        Ok(self.get_user_info(&access_token).await?.into())
+    }
+
+    async fn authorize(&self) -> Result<AuthSession, ShopError> {
+        let access_token = self.authenticate().await?;
+        let entitlements_token = self.get_entitlements_token(&access_token).await?;
+
+        let puuid = match &self.puuid {
+            Some(p) => p.clone(),
+            None => self.get_puuid(&access_token).await?,
+        };
+
+        Ok(AuthSession {
+            access_token,
+            entitlements_token,
+            puuid,
+        })
     }
 
     async fn get_storefront_raw(
@@ -261,7 +326,14 @@ impl ShopClient {
                     }
                 }
 
-                match serde_json::from_str::<ApiStorefront>(&text) {
+                // v1 nests a single bundle without totals; convert it to the common shape
+                let parsed = if url == v1 {
+                    serde_json::from_str::<ApiStorefrontV1>(&text).map(ApiStorefront::from)
+                } else {
+                    serde_json::from_str::<ApiStorefront>(&text)
+                };
+
+                match parsed {
                     Ok(data) => return Ok(data),
                     Err(_) => continue,
                 }
@@ -271,17 +343,60 @@ impl ShopClient {
         Err(ShopError::StorefrontFailed)
     }
 
-    pub(super) async fn fetch(&self, client_version: &str) -> Result<Storefront, ShopError> {
-        let access_token = self.authenticate().await?;
-        let entitlements_token = self.get_entitlements_token(&access_token).await?;
+    /// Stage-by-stage variant of `fetch` for diagnostics: each step is timed and
+    /// recorded in `stages`, stopping at the first failure. Bundle names are not fetched.
+    pub(super) async fn fetch_by_stage(
+        &self,
+        client_version: &str,
+        stages: &mut Vec<ShopStage>,
+    ) {
+        let started = std::time::Instant::now();
+        let Some(access_token) =
+            record_stage(stages, "authenticate", started, self.authenticate().await, |_| None)
+        else {
+            return;
+        };
+
+        let started = std::time::Instant::now();
+        let entitlements = self.get_entitlements_token(&access_token).await;
+        let Some(entitlements_token) =
+            record_stage(stages, "entitlements", started, entitlements, |_| None)
+        else {
+            return;
+        };
 
+        let started = std::time::Instant::now();
         let puuid = match &self.puuid {
-            Some(p) => p.clone(),
-            None => self.get_puuid(&access_token).await?,
+            Some(p) => Ok(p.clone()),
+            None => self.get_puuid(&access_token).await,
+        };
+        let Some(puuid) = record_stage(stages, "puuid", started, puuid, |p| Some(p.clone())) else {
+            return;
         };
 
+        let started = std::time::Instant::now();
         let raw = self
             .get_storefront_raw(&access_token, &entitlements_token, &puuid, client_version)
+            .await;
+        record_stage(stages, "storefront", started, raw, |raw| {
+            Some(format!(
+                "{} daily offers on shard {}",
+                raw.skins_panel_layout.single_item_offers.len(),
+                self.shard
+            ))
+        });
+    }
+
+    pub(super) async fn fetch(&self, client_version: &str) -> Result<Storefront, ShopError> {
+        let session = self.authorize().await?;
+
+        let raw = self
+            .get_storefront_raw(
+                &session.access_token,
+                &session.entitlements_token,
+                &session.puuid,
+                client_version,
+            )
             .await?;
 
         // Collect DataAssetIDs before raw is consumed by parse_storefront
@@ -311,6 +426,79 @@ impl ShopClient {
         Ok(parse_storefront(raw, bundle_names))
     }
 
+    /// Fetch the player's equipped loadout from the personalization service.
+    pub(super) async fn fetch_loadout(&self, client_version: &str) -> Result<Loadout, ShopError> {
+        let session = self.authorize().await?;
+        let url = format!(
+            "https://pd.{}.a.pvp.net/personalization/v2/players/{}/playerloadout",
+            self.shard, session.puuid
+        );
+
+        let raw: ApiLoadout = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", session.access_token))
+            .header("X-Riot-Entitlements-JWT", &session.entitlements_token)
+            .header("X-Riot-ClientPlatform", CLIENT_PLATFORM)
+            .header("X-Riot-ClientVersion", client_version)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(parse_loadout(raw))
+    }
+
+    pub(super) async fn fetch_wallet(&self, client_version: &str) -> Result<Wallet, ShopError> {
+        let session = self.authorize().await?;
+        let url = format!(
+            "https://pd.{}.a.pvp.net/store/v1/wallet/{}",
+            self.shard, session.puuid
+        );
+
+        let raw: ApiWallet = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", session.access_token))
+            .header("X-Riot-Entitlements-JWT", &session.entitlements_token)
+            .header("X-Riot-ClientPlatform", CLIENT_PLATFORM)
+            .header("X-Riot-ClientVersion", client_version)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(parse_wallet(raw))
+    }
+
+    pub(super) async fn fetch_owned_agents(
+        &self,
+        client_version: &str,
+    ) -> Result<Vec<String>, ShopError> {
+        let session = self.authorize().await?;
+        let url = format!(
+            "https://pd.{}.a.pvp.net/store/v1/entitlements/{}/{}",
+            self.shard, session.puuid, ITEM_TYPE_AGENT
+        );
+
+        let raw: ApiItemEntitlements = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", session.access_token))
+            .header("X-Riot-Entitlements-JWT", &session.entitlements_token)
+            .header("X-Riot-ClientPlatform", CLIENT_PLATFORM)
+            .header("X-Riot-ClientVersion", client_version)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(parse_owned_agents(raw))
+    }
+
     /// Extract the current cookie values from the jar after authentication.
     ///
     /// The auth flow may have updated cookies via Set-Cookie headers; this
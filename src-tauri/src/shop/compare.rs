@@ -0,0 +1,94 @@
+use serde::Serialize;
+
+use super::export::{ExportedOffer, ItemDisplay};
+use super::types::Storefront;
+
+/// Daily offers split by which of two accounts has them.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShopComparison {
+    pub only_a: Vec<ExportedOffer>,
+    pub only_b: Vec<ExportedOffer>,
+    pub common: Vec<ExportedOffer>,
+}
+
+/// Compare the daily offers of two storefronts, resolving names via `resolve`.
+///
+/// Offers keep the order they have in their own shop; `common` follows `a`.
+pub fn compare_storefronts<F>(a: &Storefront, b: &Storefront, resolve: F) -> ShopComparison
+where
+    F: Fn(&str) -> Option<ItemDisplay>,
+{
+    let offer = |uuid: &str, vp_cost: u64| {
+        let display = resolve(uuid);
+        ExportedOffer {
+            uuid: uuid.to_string(),
+            name: display.as_ref().and_then(|d| d.name.clone()),
+            icon: display.and_then(|d| d.icon),
+            vp_cost,
+        }
+    };
+    let in_shop = |shop: &Storefront, uuid: &str| {
+        shop.daily_offers
+            .iter()
+            .any(|o| o.skin_uuid.eq_ignore_ascii_case(uuid))
+    };
+
+    let mut comparison = ShopComparison {
+        only_a: Vec::new(),
+        only_b: Vec::new(),
+        common: Vec::new(),
+    };
+
+    for o in &a.daily_offers {
+        if in_shop(b, &o.skin_uuid) {
+            comparison.common.push(offer(&o.skin_uuid, o.vp_cost));
+        } else {
+            comparison.only_a.push(offer(&o.skin_uuid, o.vp_cost));
+        }
+    }
+
+    for o in &b.daily_offers {
+        if !in_shop(a, &o.skin_uuid) {
+            comparison.only_b.push(offer(&o.skin_uuid, o.vp_cost));
+        }
+    }
+
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shop::types::DailyOffer;
+
+    fn shop(uuids: &[&str]) -> Storefront {
+        Storefront {
+            daily_offers: uuids
+                .iter()
+                .map(|u| DailyOffer { skin_uuid: u.to_string(), vp_cost: 875 })
+                .collect(),
+            daily_remaining_secs: 0,
+            bundles: None,
+            night_market: None,
+            night_market_remaining_secs: None,
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_storefronts() {
+        let a = shop(&["x", "y", "z"]);
+        let b = shop(&["Z", "w"]);
+        let comparison = compare_storefronts(&a, &b, |_| None);
+
+        let uuids = |offers: &[ExportedOffer]| {
+            offers.iter().map(|o| o.uuid.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(uuids(&comparison.only_a), vec!["x", "y"]);
+        assert_eq!(uuids(&comparison.only_b), vec!["w"]);
+        assert_eq!(uuids(&comparison.common), vec!["z"]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::client::ShopClient;
+use super::error::ShopError;
+use super::types::RiotCookies;
+use super::version::fetch_version_info;
+
+/// Outcome of one stage of the shop pipeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShopStage {
+    pub stage: &'static str,
+    pub ok: bool,
+    pub elapsed_ms: u64,
+    /// The error on failure, or a short summary of what the stage returned.
+    pub detail: Option<String>,
+}
+
+/// Stage-by-stage report from `diagnose_shop`. Stages after a failure are not run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShopDiagnosis {
+    pub stages: Vec<ShopStage>,
+    pub failed_stage: Option<&'static str>,
+}
+
+/// Push the outcome of a stage that started at `started` and pass its value on.
+pub(super) fn record_stage<T>(
+    stages: &mut Vec<ShopStage>,
+    stage: &'static str,
+    started: Instant,
+    result: Result<T, ShopError>,
+    summary: impl FnOnce(&T) -> Option<String>,
+) -> Option<T> {
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let (ok, detail, value) = match result {
+        Ok(value) => (true, summary(&value), Some(value)),
+        Err(e) => (false, Some(e.to_string()), None),
+    };
+    log::debug!("diagnose_shop: {} ok={} in {}ms", stage, ok, elapsed_ms);
+    stages.push(ShopStage {
+        stage,
+        ok,
+        elapsed_ms,
+        detail,
+    });
+    value
+}
+
+/// Run version → authenticate → entitlements → puuid → storefront once, timing each stage.
+///
+/// Nothing is cached; the refreshed cookies are returned so the caller can
+/// still persist them. `region` overrides the shard derived from `clid`.
+pub async fn diagnose_shop(
+    cookies: RiotCookies,
+    region: Option<&str>,
+) -> (ShopDiagnosis, Option<RiotCookies>) {
+    let mut stages = Vec::new();
+
+    let info = record_stage(
+        &mut stages,
+        "version",
+        Instant::now(),
+        fetch_version_info().await,
+        |info| Some(info.client_version.clone()),
+    );
+
+    let mut updated_cookies = None;
+    if let Some(info) = info {
+        let started = Instant::now();
+        if let Some(mut client) = record_stage(
+            &mut stages,
+            "client",
+            started,
+            ShopClient::new(cookies, &info.user_agent),
+            |_| None,
+        ) {
+            if let Some(region) = region {
+                client.set_region(region);
+            }
+            client.fetch_by_stage(&info.client_version, &mut stages).await;
+            updated_cookies = Some(client.extract_updated_cookies());
+        }
+    }
+
+    let failed_stage = stages.iter().find(|s| !s.ok).map(|s| s.stage);
+    (ShopDiagnosis { stages, failed_stage }, updated_cookies)
+}
@@ -0,0 +1,184 @@
+use serde::Serialize;
+
+use super::types::Storefront;
+
+/// Display name and icon for a cosmetic, as resolved from the skins database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDisplay {
+    pub name: Option<String>,
+    pub icon: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedOffer {
+    pub uuid: String,
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub vp_cost: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedNightMarketOffer {
+    pub uuid: String,
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub base_cost: u64,
+    pub discount_cost: u64,
+    pub discount_percent: f64,
+}
+
+/// A featured bundle with its items resolved. `vp_cost` on items is the discounted price.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedBundle {
+    pub name: String,
+    pub total_base_cost: u64,
+    pub total_discounted_cost: u64,
+    pub total_discount_percent: f64,
+    pub expires_at: i64,
+    pub items: Vec<ExportedOffer>,
+}
+
+/// Self-contained snapshot of a shop for rendering a shareable card.
+///
+/// All `*_at` fields are absolute UNIX seconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShopExport {
+    pub account_id: i64,
+    pub generated_at: i64,
+    pub daily_reset_at: i64,
+    pub daily_offers: Vec<ExportedOffer>,
+    pub night_market: Option<Vec<ExportedNightMarketOffer>>,
+    pub night_market_reset_at: Option<i64>,
+    pub bundles: Vec<ExportedBundle>,
+}
+
+/// Build a `ShopExport` from a storefront, resolving names and icons via `resolve`.
+///
+/// `now` is the UNIX time the storefront's remaining durations are relative to.
+pub fn build_shop_export<F>(account_id: i64, storefront: &Storefront, now: i64, resolve: F) -> ShopExport
+where
+    F: Fn(&str) -> Option<ItemDisplay>,
+{
+    let offer = |uuid: &str, vp_cost: u64| {
+        let display = resolve(uuid);
+        ExportedOffer {
+            uuid: uuid.to_string(),
+            name: display.as_ref().and_then(|d| d.name.clone()),
+            icon: display.and_then(|d| d.icon),
+            vp_cost,
+        }
+    };
+
+    let daily_offers = storefront
+        .daily_offers
+        .iter()
+        .map(|o| offer(&o.skin_uuid, o.vp_cost))
+        .collect();
+
+    // Cached offers outlive the night market; as in `store_timers`, drop them once it has no timer
+    let night_market = storefront
+        .night_market
+        .as_ref()
+        .filter(|_| storefront.night_market_remaining_secs.is_some())
+        .map(|offers| {
+            offers
+                .iter()
+                .map(|o| {
+                    let display = resolve(&o.skin_uuid);
+                    ExportedNightMarketOffer {
+                        uuid: o.skin_uuid.clone(),
+                        name: display.as_ref().and_then(|d| d.name.clone()),
+                        icon: display.and_then(|d| d.icon),
+                        base_cost: o.base_cost,
+                        discount_cost: o.discount_cost,
+                        discount_percent: o.discount_percent,
+                    }
+                })
+                .collect()
+        });
+
+    let bundles = storefront
+        .bundles
+        .iter()
+        .flatten()
+        .map(|b| ExportedBundle {
+            name: b.name.clone(),
+            total_base_cost: b.total_base_cost,
+            total_discounted_cost: b.total_discounted_cost,
+            total_discount_percent: b.total_discount_percent,
+            expires_at: now + b.bundle_remaining_secs as i64,
+            items: b
+                .items
+                .iter()
+                .map(|i| offer(&i.item_uuid, i.discounted_cost))
+                .collect(),
+        })
+        .collect();
+
+    ShopExport {
+        account_id,
+        generated_at: now,
+        daily_reset_at: now + storefront.daily_remaining_secs as i64,
+        daily_offers,
+        night_market,
+        night_market_reset_at: storefront
+            .night_market_remaining_secs
+            .map(|secs| now + secs as i64),
+        bundles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shop::types::{DailyOffer, NightMarketOffer};
+
+    #[test]
+    fn test_build_shop_export_resolves_items() {
+        let storefront = Storefront {
+            daily_offers: vec![
+                DailyOffer { skin_uuid: "known".to_string(), vp_cost: 1775 },
+                DailyOffer { skin_uuid: "unknown".to_string(), vp_cost: 875 },
+            ],
+            daily_remaining_secs: 3600,
+            bundles: None,
+            night_market: Some(vec![NightMarketOffer {
+                skin_uuid: "known".to_string(),
+                base_cost: 1775,
+                discount_cost: 1065,
+                discount_percent: 40.0,
+            }]),
+            night_market_remaining_secs: Some(7200),
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        };
+
+        let export = build_shop_export(7, &storefront, 1_000, |uuid| {
+            (uuid == "known").then(|| ItemDisplay {
+                name: Some("Prime Vandal".to_string()),
+                icon: Some("https://example.com/icon.png".to_string()),
+            })
+        });
+
+        assert_eq!(export.account_id, 7);
+        assert_eq!(export.daily_reset_at, 4_600);
+        assert_eq!(export.night_market_reset_at, Some(8_200));
+        assert_eq!(export.daily_offers[0].name.as_deref(), Some("Prime Vandal"));
+        assert_eq!(export.daily_offers[1].name, None);
+        assert_eq!(export.daily_offers[1].vp_cost, 875);
+        let night = export.night_market.unwrap();
+        assert_eq!(night[0].discount_cost, 1065);
+        assert!(export.bundles.is_empty());
+
+        // An ended night market left in the cache has no timer and is not exported.
+        let ended = Storefront {
+            night_market_remaining_secs: None,
+            ..storefront
+        };
+        let export = build_shop_export(7, &ended, 1_000, |_| None);
+        assert!(export.night_market.is_none());
+        assert_eq!(export.night_market_reset_at, None);
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::types::Value;
+use serde::Serialize;
+
+use crate::db;
+
+use super::cache::daily_cache_ttl_secs;
+use super::storefront::ITEM_TYPE_SKIN;
+use super::types::Storefront;
+
+/// One live storefront fetch recorded in `storefront_history`.
+pub struct HistoryEntry {
+    pub account_id: i64,
+    pub fetched_at: i64,
+    pub storefront: Storefront,
+}
+
+/// Serialize a storefront for `storefront_history.storefront_json`.
+///
+/// Uncompressed entries are stored as TEXT and compressed ones as a gzip BLOB,
+/// so rows written under either setting can live side by side.
+fn encode_storefront(storefront: &Storefront, compress: bool) -> Result<Value, String> {
+    let json = serde_json::to_string(storefront).map_err(|e| e.to_string())?;
+    if !compress {
+        return Ok(Value::Text(json));
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+    encoder.finish().map(Value::Blob).map_err(|e| e.to_string())
+}
+
+/// Parse a stored history entry, whichever format it was written in.
+fn decode_storefront(value: Value) -> Result<Storefront, String> {
+    match value {
+        Value::Text(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Value::Blob(bytes) => {
+            let mut json = String::new();
+            GzDecoder::new(bytes.as_slice())
+                .read_to_string(&mut json)
+                .map_err(|e| e.to_string())?;
+            serde_json::from_str(&json).map_err(|e| e.to_string())
+        }
+        other => Err(format!("unexpected column type {:?}", other.data_type())),
+    }
+}
+
+/// Most history entries kept per account; older ones are pruned on insert.
+const MAX_HISTORY_PER_ACCOUNT: i64 = 1000;
+
+/// Append a freshly fetched storefront to the history. Errors are logged, never propagated.
+///
+/// The entry is gzip-compressed when the `compress_history` setting is on. Only
+/// the newest `MAX_HISTORY_PER_ACCOUNT` entries of the account are kept.
+pub fn record_storefront_history(account_id: i64, storefront: &Storefront) {
+    let compress = db::get_settings().map(|s| s.compress_history).unwrap_or(false);
+    let value = match encode_storefront(storefront, compress) {
+        Ok(v) => v,
+        Err(e) => {
+            log::warn!("History: failed to serialize storefront: {}", e);
+            return;
+        }
+    };
+
+    let result = db::init::get_connection(None).and_then(|conn| {
+        conn.execute(
+            "INSERT INTO storefront_history (account_id, storefront_json, fetched_at)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![account_id, value, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| e.to_string())?;
+        prune_history_with(&conn, account_id, MAX_HISTORY_PER_ACCOUNT)
+    });
+
+    if let Err(e) = result {
+        log::warn!("History: failed to record storefront for account {}: {}", account_id, e);
+    }
+}
+
+/// Delete all but the newest `keep` history entries of an account.
+fn prune_history_with(conn: &rusqlite::Connection, account_id: i64, keep: i64) -> Result<usize, String> {
+    conn.execute(
+        "DELETE FROM storefront_history
+          WHERE account_id = ?1
+            AND id NOT IN (
+                SELECT id FROM storefront_history
+                 WHERE account_id = ?1
+                 ORDER BY fetched_at DESC, id DESC
+                 LIMIT ?2
+            )",
+        rusqlite::params![account_id, keep],
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Load every recorded storefront, oldest first, decompressing entries as needed.
+/// Unparseable rows are skipped.
+pub fn load_storefront_history() -> Result<Vec<HistoryEntry>, String> {
+    load_history_entries(None)
+}
+
+/// Like `load_storefront_history`, limited to one account when `account_id` is set.
+fn load_history_entries(account_id: Option<i64>) -> Result<Vec<HistoryEntry>, String> {
+    let conn = db::init::get_connection(None)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT account_id, storefront_json, fetched_at FROM storefront_history
+             WHERE ?1 IS NULL OR account_id = ?1
+             ORDER BY fetched_at ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([account_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Value>(1)?, row.get::<_, i64>(2)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(account_id, value, fetched_at)| {
+            match decode_storefront(value) {
+                Ok(storefront) => Some(HistoryEntry {
+                    account_id,
+                    fetched_at,
+                    storefront,
+                }),
+                Err(e) => {
+                    log::warn!("History: skipping unparseable row for account {}: {}", account_id, e);
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// One skin offer seen in the recorded storefront history.
+///
+/// `discount_percent` is `None` for daily offers, which are never discounted.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ShopHistoryRow {
+    pub account_id: i64,
+    pub skin_uuid: String,
+    /// `daily`, `night_market` or `bundle`.
+    pub kind: String,
+    pub vp_cost: u64,
+    pub discount_percent: Option<f64>,
+    pub seen_at: i64,
+}
+
+/// Flatten one recorded storefront into `(row, expires_at)` pairs.
+///
+/// Expiries come from the absolute timestamps when recorded, with the daily
+/// one clamped like the cache TTL. Night market offers without an expiry and
+/// bundle items that are not weapon skins are left out.
+fn entry_offers(entry: &HistoryEntry) -> Vec<(ShopHistoryRow, i64)> {
+    let sf = &entry.storefront;
+    let seen_at = entry.fetched_at;
+    let row = |skin_uuid: &str, kind: &str, vp_cost: u64, discount_percent: Option<f64>| ShopHistoryRow {
+        account_id: entry.account_id,
+        skin_uuid: skin_uuid.to_string(),
+        kind: kind.to_string(),
+        vp_cost,
+        discount_percent,
+        seen_at,
+    };
+
+    let daily_remaining = if sf.daily_expires_at > 0 {
+        sf.daily_expires_at.saturating_sub(seen_at).max(0) as u64
+    } else {
+        sf.daily_remaining_secs
+    };
+    let daily_expires_at = seen_at + daily_cache_ttl_secs(daily_remaining, seen_at) as i64;
+    let nm_expires_at = sf
+        .night_market_expires_at
+        .or_else(|| sf.night_market_remaining_secs.map(|secs| seen_at + secs as i64))
+        .filter(|&at| at > seen_at);
+
+    let mut offers: Vec<(ShopHistoryRow, i64)> = sf
+        .daily_offers
+        .iter()
+        .map(|o| (row(&o.skin_uuid, "daily", o.vp_cost, None), daily_expires_at))
+        .collect();
+
+    if let Some(expires_at) = nm_expires_at {
+        offers.extend(sf.night_market.iter().flatten().map(|o| {
+            let r = row(&o.skin_uuid, "night_market", o.discount_cost, Some(o.discount_percent));
+            (r, expires_at)
+        }));
+    }
+
+    for bundle in sf.bundles.iter().flatten().filter(|b| b.bundle_remaining_secs > 0) {
+        let expires_at = seen_at + bundle.bundle_remaining_secs as i64;
+        offers.extend(
+            bundle
+                .items
+                .iter()
+                .filter(|i| i.item_type_id.eq_ignore_ascii_case(ITEM_TYPE_SKIN))
+                .map(|i| {
+                    let r = row(&i.item_uuid, "bundle", i.discounted_cost, Some(i.discount_percent));
+                    (r, expires_at)
+                }),
+        );
+    }
+
+    offers
+}
+
+/// Every skin offer in `entries` (oldest first), once per rotation, newest first.
+///
+/// An offer is skipped when the same skin was already seen as the same kind
+/// in a rotation that had not ended yet, so refetches mid-rotation add nothing.
+fn shop_history_rows(entries: &[HistoryEntry], skin_uuid: Option<&str>) -> Vec<ShopHistoryRow> {
+    let mut active_until: HashMap<(i64, String, String), i64> = HashMap::new();
+    let mut rows = Vec::new();
+
+    for entry in entries {
+        for (row, expires_at) in entry_offers(entry) {
+            if skin_uuid.is_some_and(|uuid| !row.skin_uuid.eq_ignore_ascii_case(uuid)) {
+                continue;
+            }
+            let key = (row.account_id, row.skin_uuid.to_lowercase(), row.kind.clone());
+            if active_until.get(&key).is_some_and(|&until| until > row.seen_at) {
+                continue;
+            }
+            active_until.insert(key, expires_at);
+            rows.push(row);
+        }
+    }
+
+    rows.reverse();
+    rows
+}
+
+/// An account's skin offers from the storefront history, newest first,
+/// optionally for one skin only.
+pub fn load_shop_history(
+    account_id: i64,
+    skin_uuid: Option<&str>,
+) -> Result<Vec<ShopHistoryRow>, String> {
+    let entries = load_history_entries(Some(account_id))?;
+    Ok(shop_history_rows(&entries, skin_uuid))
+}
+
+/// Quote a CSV field when it contains a separator, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render history as CSV with one row per daily or night market offer.
+///
+/// `account_names` maps account ids to a display name (unknown ids fall back to
+/// the id) and `skin_names` maps offer UUIDs to skin names (unknown skins fall
+/// back to the UUID).
+pub fn history_to_csv(
+    entries: &[HistoryEntry],
+    account_names: &HashMap<i64, String>,
+    skin_names: &HashMap<String, String>,
+) -> String {
+    let mut csv = String::from("account,kind,skin,skin_uuid,vp_cost,date\n");
+
+    for entry in entries {
+        let account = account_names
+            .get(&entry.account_id)
+            .cloned()
+            .unwrap_or_else(|| entry.account_id.to_string());
+        let date = chrono::DateTime::from_timestamp(entry.fetched_at, 0)
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+
+        let daily = entry
+            .storefront
+            .daily_offers
+            .iter()
+            .map(|o| ("daily", &o.skin_uuid, o.vp_cost));
+        let night_market = entry
+            .storefront
+            .night_market
+            .iter()
+            .flatten()
+            .map(|o| ("night_market", &o.skin_uuid, o.discount_cost));
+
+        for (kind, uuid, vp_cost) in daily.chain(night_market) {
+            let skin = skin_names.get(uuid).unwrap_or(uuid);
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(&account),
+                kind,
+                csv_field(skin),
+                csv_field(uuid),
+                vp_cost,
+                date
+            ));
+        }
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shop::types::{Bundle, BundleItem, DailyOffer, NightMarketOffer};
+
+    fn sample_storefront() -> Storefront {
+        Storefront {
+            daily_offers: vec![
+                DailyOffer { skin_uuid: "known".to_string(), vp_cost: 1775 },
+                DailyOffer { skin_uuid: "unknown".to_string(), vp_cost: 875 },
+            ],
+            daily_remaining_secs: 0,
+            bundles: None,
+            night_market: Some(vec![NightMarketOffer {
+                skin_uuid: "known".to_string(),
+                base_cost: 1775,
+                discount_cost: 1065,
+                discount_percent: 40.0,
+            }]),
+            night_market_remaining_secs: None,
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_storefront_roundtrip_both_formats() {
+        let storefront = sample_storefront();
+
+        let plain = encode_storefront(&storefront, false).unwrap();
+        assert!(matches!(plain, Value::Text(_)));
+        let decoded = decode_storefront(plain).unwrap();
+        assert_eq!(serde_json::to_value(decoded).unwrap(), serde_json::to_value(&storefront).unwrap());
+
+        let compressed = encode_storefront(&storefront, true).unwrap();
+        assert!(matches!(compressed, Value::Blob(_)));
+        let decoded = decode_storefront(compressed).unwrap();
+        assert_eq!(serde_json::to_value(decoded).unwrap(), serde_json::to_value(&storefront).unwrap());
+    }
+
+    #[test]
+    fn test_shop_history_rows_once_per_rotation() {
+        let mut storefront = sample_storefront();
+        storefront.daily_remaining_secs = 3600;
+        storefront.night_market_remaining_secs = Some(7200);
+        storefront.bundles = Some(vec![Bundle {
+            name: "Bundle".to_string(),
+            total_base_cost: 0,
+            total_discounted_cost: 0,
+            total_discount_percent: 0.0,
+            bundle_remaining_secs: 86_400,
+            items: vec![
+                BundleItem {
+                    item_uuid: "bundle-skin".to_string(),
+                    item_type_id: ITEM_TYPE_SKIN.to_string(),
+                    base_cost: 1775,
+                    discounted_cost: 1775,
+                    discount_percent: 0.0,
+                },
+                BundleItem {
+                    item_uuid: "bundle-buddy".to_string(),
+                    item_type_id: "dd3bf334-87f3-40bd-b043-682a57a8dc3a".to_string(),
+                    base_cost: 475,
+                    discounted_cost: 475,
+                    discount_percent: 0.0,
+                },
+            ],
+        }]);
+        // No absolute timestamps recorded, as in history written before they existed
+        let at = |fetched_at| HistoryEntry { account_id: 1, fetched_at, storefront: storefront.clone() };
+
+        // Refetched mid-rotation at 2000; daily rotated by 5000, night market still running
+        let entries = vec![at(1_704_088_800), at(1_704_088_900), at(1_704_088_800 + 4_000)];
+        let rows = shop_history_rows(&entries, None);
+        assert_eq!(rows.len(), 6);
+        assert!(rows.iter().all(|r| r.skin_uuid != "bundle-buddy"));
+
+        let known = shop_history_rows(&entries, Some("KNOWN"));
+        assert_eq!(
+            known.iter().map(|r| (r.kind.as_str(), r.seen_at)).collect::<Vec<_>>(),
+            vec![
+                ("daily", 1_704_092_800),
+                ("night_market", 1_704_088_800),
+                ("daily", 1_704_088_800),
+            ]
+        );
+        assert_eq!(known[1].vp_cost, 1065);
+        assert_eq!(known[1].discount_percent, Some(40.0));
+        assert_eq!(known[2].discount_percent, None);
+    }
+
+    #[test]
+    fn test_shop_history_zero_duration_and_missing_night_market_expiry() {
+        let mut storefront = sample_storefront();
+        // v1 responses can report a zero daily duration and no night market duration
+        storefront.daily_remaining_secs = 0;
+        storefront.night_market_remaining_secs = None;
+        let at = |fetched_at| HistoryEntry { account_id: 1, fetched_at, storefront: storefront.clone() };
+
+        let rows = shop_history_rows(&[at(1_704_088_800), at(1_704_088_860)], None);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.kind == "daily" && r.seen_at == 1_704_088_800));
+    }
+
+    #[test]
+    fn test_prune_history_keeps_newest() {
+        let db_path = std::env::temp_dir().join("test_prune_history_keeps_newest.db");
+        if db_path.exists() {
+            std::fs::remove_file(&db_path).unwrap();
+        }
+
+        {
+            let conn = db::init::initialize_database(Some(db_path.clone())).unwrap();
+            for (account_id, fetched_at) in [(1, 10), (1, 30), (1, 20), (2, 5)] {
+                conn.execute(
+                    "INSERT INTO storefront_history (account_id, storefront_json, fetched_at)
+                     VALUES (?1, '{}', ?2)",
+                    [account_id, fetched_at],
+                )
+                .unwrap();
+            }
+
+            assert_eq!(prune_history_with(&conn, 1, 2).unwrap(), 1);
+            let kept: Vec<i64> = conn
+                .prepare("SELECT fetched_at FROM storefront_history ORDER BY fetched_at")
+                .unwrap()
+                .query_map([], |r| r.get(0))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            assert_eq!(kept, vec![5, 20, 30]);
+        }
+
+        std::fs::remove_file(&db_path).unwrap();
+    }
+
+    #[test]
+    fn test_history_to_csv() {
+        let entries = vec![HistoryEntry {
+            account_id: 1,
+            // 2024-01-01 00:00:00 UTC
+            fetched_at: 1_704_067_200,
+            storefront: sample_storefront(),
+        }];
+        let accounts = HashMap::from([(1, "Player#JP1".to_string())]);
+        let skins = HashMap::from([("known".to_string(), "Prime, Vandal".to_string())]);
+
+        assert_eq!(
+            history_to_csv(&entries, &accounts, &skins),
+            "account,kind,skin,skin_uuid,vp_cost,date\n\
+             Player#JP1,daily,\"Prime, Vandal\",known,1775,2024-01-01 00:00:00\n\
+             Player#JP1,daily,unknown,unknown,875,2024-01-01 00:00:00\n\
+             Player#JP1,night_market,\"Prime, Vandal\",known,1065,2024-01-01 00:00:00\n"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// A weapon's equipped skin, as reported by the personalization service.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EquippedGun {
+    pub weapon_uuid: String,
+    pub skin_uuid: String,
+    pub skin_level_uuid: String,
+    pub chroma_uuid: String,
+    pub buddy_level_uuid: Option<String>,
+}
+
+/// The account's current loadout, keyed by UUIDs only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Loadout {
+    pub guns: Vec<EquippedGun>,
+    pub spray_level_uuids: Vec<String>,
+    pub player_card_uuid: Option<String>,
+    pub player_title_uuid: Option<String>,
+}
+
+// -- Internal API response types ----------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct ApiLoadout {
+    #[serde(rename = "Guns")]
+    guns: Vec<ApiGun>,
+    #[serde(rename = "Sprays", default)]
+    sprays: Vec<ApiSpray>,
+    #[serde(rename = "Identity")]
+    identity: Option<ApiIdentity>,
+}
+
+#[derive(Deserialize)]
+struct ApiGun {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "SkinID")]
+    skin_id: String,
+    #[serde(rename = "SkinLevelID")]
+    skin_level_id: String,
+    #[serde(rename = "ChromaID")]
+    chroma_id: String,
+    #[serde(rename = "CharmLevelID")]
+    charm_level_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiSpray {
+    #[serde(rename = "SprayLevelID")]
+    spray_level_id: Option<String>,
+    #[serde(rename = "SprayID")]
+    spray_id: String,
+}
+
+#[derive(Deserialize)]
+struct ApiIdentity {
+    #[serde(rename = "PlayerCardID")]
+    player_card_id: Option<String>,
+    #[serde(rename = "PlayerTitleID")]
+    player_title_id: Option<String>,
+}
+
+/// Flatten the personalization API response into a `Loadout`.
+///
+/// Sprays without a level fall back to the spray UUID itself.
+pub(super) fn parse_loadout(raw: ApiLoadout) -> Loadout {
+    let guns = raw
+        .guns
+        .into_iter()
+        .map(|g| EquippedGun {
+            weapon_uuid: g.id,
+            skin_uuid: g.skin_id,
+            skin_level_uuid: g.skin_level_id,
+            chroma_uuid: g.chroma_id,
+            buddy_level_uuid: g.charm_level_id.filter(|id| !id.is_empty()),
+        })
+        .collect();
+
+    let spray_level_uuids = raw
+        .sprays
+        .into_iter()
+        .map(|s| s.spray_level_id.filter(|id| !id.is_empty()).unwrap_or(s.spray_id))
+        .collect();
+
+    let (player_card_uuid, player_title_uuid) = match raw.identity {
+        Some(identity) => (identity.player_card_id, identity.player_title_id),
+        None => (None, None),
+    };
+
+    Loadout {
+        guns,
+        spray_level_uuids,
+        player_card_uuid,
+        player_title_uuid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "Subject": "puuid",
+        "Version": 12,
+        "Guns": [
+            {
+                "ID": "9c82e19d-4575-0200-1a81-3eacf00cf872",
+                "SkinID": "skin-a",
+                "SkinLevelID": "level-a",
+                "ChromaID": "chroma-a",
+                "CharmInstanceID": "instance",
+                "CharmID": "buddy",
+                "CharmLevelID": "buddy-level",
+                "Attachments": []
+            },
+            {
+                "ID": "29a0cfab-485b-f5d5-779a-b59f85e204a8",
+                "SkinID": "skin-b",
+                "SkinLevelID": "level-b",
+                "ChromaID": "chroma-b",
+                "Attachments": []
+            }
+        ],
+        "Sprays": [
+            { "EquipSlotID": "slot", "SprayID": "spray", "SprayLevelID": null }
+        ],
+        "Identity": {
+            "PlayerCardID": "card",
+            "PlayerTitleID": "title",
+            "AccountLevel": 42
+        },
+        "Incognito": false
+    }"#;
+
+    #[test]
+    fn test_parse_loadout() {
+        let raw: ApiLoadout = serde_json::from_str(SAMPLE).unwrap();
+        let loadout = parse_loadout(raw);
+
+        assert_eq!(loadout.guns.len(), 2);
+        assert_eq!(loadout.guns[0].skin_level_uuid, "level-a");
+        assert_eq!(loadout.guns[0].buddy_level_uuid.as_deref(), Some("buddy-level"));
+        assert_eq!(loadout.guns[1].buddy_level_uuid, None);
+        assert_eq!(loadout.spray_level_uuids, vec!["spray".to_string()]);
+        assert_eq!(loadout.player_card_uuid.as_deref(), Some("card"));
+        assert_eq!(loadout.player_title_uuid.as_deref(), Some("title"));
+    }
+}
@@ -1,36 +1,93 @@
+mod agents;
+mod alerts;
 mod cache;
 mod client;
+mod compare;
+mod diagnose;
 mod error;
+mod export;
+mod history;
+mod loadout;
+mod reset;
+mod session;
 mod storefront;
+mod summary;
 mod types;
 mod version;
+mod wallet;
 
-pub use cache::{load_cached_storefront, save_storefront_cache};
+pub use alerts::{bundle_discount_alerts, BundleDiscountAlert};
+pub use cache::{
+    clear_storefront_cache, list_cached_storefronts, load_cached_loadout, load_cached_owned_agents,
+    load_cached_storefront, save_loadout_cache, save_owned_agents_cache, save_storefront_cache,
+};
+pub use compare::{compare_storefronts, ShopComparison};
+pub use diagnose::{diagnose_shop, ShopDiagnosis, ShopStage};
 pub use error::ShopError;
+pub use export::{build_shop_export, ItemDisplay, ShopExport};
+pub use history::{
+    history_to_csv, load_shop_history, load_storefront_history, record_storefront_history,
+    HistoryEntry, ShopHistoryRow,
+};
+pub use loadout::Loadout;
+pub use reset::secs_until_daily_reset;
+pub use wallet::Wallet;
+pub use session::ssid_expiry;
+pub use summary::{
+    hide_costs, night_market_savings, store_timers, BundleTimer, NightMarketSavings, StoreTimers,
+};
 #[allow(unused_imports)]
-pub use types::{Bundle, BundleItem, DailyOffer, NightMarketOffer, RiotCookies, Storefront};
+pub use types::{
+    AccessoryOffer, Bundle, BundleItem, CachedShopInfo, DailyOffer, NightMarketOffer, RiotCookies,
+    RiotIdentity, Storefront,
+};
 
+pub use client::region_from_clid;
 use client::ShopClient;
-use version::fetch_version_info;
+use version::{fetch_version_info, pinned_version_info, VersionInfo};
 
 /// Fetch the Valorant daily shop and night market using account cookies.
 ///
 /// # Arguments
 /// * `cookies` - Riot account cookies parsed from RiotGamesPrivateSettings.yaml.
+/// * `region` - Region (e.g. "eu", "br") overriding the shard derived from `clid`.
 ///
-/// The shard is derived from `clid` (e.g. "ap1" -> "ap") and the PUUID from `sub`.
-pub async fn fetch_storefront(
+/// Without `region` the shard is derived from `clid` (e.g. "ap1" -> "ap"); the
+/// PUUID comes from `sub`.
+pub async fn fetch_storefront_in_region(
     cookies: RiotCookies,
+    region: Option<&str>,
 ) -> Result<(Storefront, RiotCookies), ShopError> {
     log::debug!("fetch_storefront: starting version info fetch");
     let info = fetch_version_info().await?;
+    fetch_storefront_with_info(cookies, region, info).await
+}
+
+/// Like `fetch_storefront_in_region`, but sends `client_version` instead of the auto-detected one.
+///
+/// Useful during a patch window when the storefront rejects the version
+/// valorant-api.com still reports.
+pub async fn fetch_storefront_with_version(
+    cookies: RiotCookies,
+    client_version: String,
+    region: Option<&str>,
+) -> Result<(Storefront, RiotCookies), ShopError> {
+    let info = pinned_version_info(client_version).await;
+    fetch_storefront_with_info(cookies, region, info).await
+}
+
+async fn fetch_storefront_with_info(
+    cookies: RiotCookies,
+    region: Option<&str>,
+    info: VersionInfo,
+) -> Result<(Storefront, RiotCookies), ShopError> {
     log::debug!(
         "fetch_storefront: version={}, user_agent={}",
         info.client_version,
         info.user_agent
     );
 
-    let shop_client = ShopClient::new(cookies, &info.user_agent)?;
+    let shop_client = region_client(cookies, &info.user_agent, region)?;
     log::debug!("fetch_storefront: ShopClient created, fetching storefront");
 
     let storefront = shop_client.fetch(&info.client_version).await?;
@@ -44,19 +101,136 @@ pub async fn fetch_storefront(
     Ok((storefront, updated_cookies))
 }
 
+/// Build a `ShopClient` whose shard is `region` when given, else the one derived from `clid`.
+fn region_client(
+    cookies: RiotCookies,
+    user_agent: &str,
+    region: Option<&str>,
+) -> Result<ShopClient, ShopError> {
+    let mut shop_client = ShopClient::new(cookies, user_agent)?;
+    if let Some(region) = region {
+        shop_client.set_region(region);
+    }
+    Ok(shop_client)
+}
+
+/// Fetch the account's equipped loadout using account cookies.
+///
+/// Returns the loadout together with the refreshed cookies, like `fetch_storefront_in_region`.
+pub async fn fetch_loadout(
+    cookies: RiotCookies,
+    region: Option<&str>,
+) -> Result<(Loadout, RiotCookies), ShopError> {
+    let info = fetch_version_info().await?;
+    let shop_client = region_client(cookies, &info.user_agent, region)?;
+
+    let loadout = shop_client.fetch_loadout(&info.client_version).await?;
+    log::debug!("fetch_loadout: {} guns equipped", loadout.guns.len());
+
+    let updated_cookies = shop_client.extract_updated_cookies();
+    Ok((loadout, updated_cookies))
+}
+
+/// Fetch the account's VP, Radianite and Kingdom Credits balances.
+///
+/// Returns the wallet together with the refreshed cookies, like `fetch_storefront_in_region`.
+pub async fn fetch_wallet(
+    cookies: RiotCookies,
+    region: Option<&str>,
+) -> Result<(Wallet, RiotCookies), ShopError> {
+    let info = fetch_version_info().await?;
+    let shop_client = region_client(cookies, &info.user_agent, region)?;
+
+    let wallet = shop_client.fetch_wallet(&info.client_version).await?;
+    log::debug!("fetch_wallet: {} VP", wallet.vp);
+
+    let updated_cookies = shop_client.extract_updated_cookies();
+    Ok((wallet, updated_cookies))
+}
+
+/// Fetch the UUIDs of agents the account has unlocked through entitlements.
+///
+/// Returns the UUIDs together with the refreshed cookies, like `fetch_storefront_in_region`.
+pub async fn fetch_owned_agents(
+    cookies: RiotCookies,
+    region: Option<&str>,
+) -> Result<(Vec<String>, RiotCookies), ShopError> {
+    let info = fetch_version_info().await?;
+    let shop_client = region_client(cookies, &info.user_agent, region)?;
+
+    let agents = shop_client.fetch_owned_agents(&info.client_version).await?;
+    log::debug!("fetch_owned_agents: {} agent entitlements", agents.len());
+
+    let updated_cookies = shop_client.extract_updated_cookies();
+    Ok((agents, updated_cookies))
+}
+
+/// Fetch the Riot ID the cookies are currently logged in as.
+///
+/// Returns the identity together with the refreshed cookies, like `fetch_storefront_in_region`.
+pub async fn fetch_identity(
+    cookies: RiotCookies,
+) -> Result<(RiotIdentity, RiotCookies), ShopError> {
+    let info = fetch_version_info().await?;
+    let shop_client = ShopClient::new(cookies, &info.user_agent)?;
+
+    let identity = shop_client.fetch_identity().await?;
+    log::debug!(
+        "fetch_identity: {:?}#{:?}",
+        identity.game_name,
+        identity.tag_line
+    );
+
+    let updated_cookies = shop_client.extract_updated_cookies();
+    Ok((identity, updated_cookies))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_identity_from_user_info() {
+        let json = r#"{
+            "sub": "puuid-1",
+            "acct": { "type": 0, "state": "ENABLED", "game_name": "Player", "tag_line": "JP1" }
+        }"#;
+        let info: types::UserInfoResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            RiotIdentity::from(info),
+            RiotIdentity {
+                puuid: "puuid-1".to_string(),
+                game_name: Some("Player".to_string()),
+                tag_line: Some("JP1".to_string()),
+            }
+        );
+
+        let info: types::UserInfoResponse = serde_json::from_str(r#"{"sub": "puuid-2"}"#).unwrap();
+        let identity = RiotIdentity::from(info);
+        assert_eq!(identity.puuid, "puuid-2");
+        assert_eq!(identity.game_name, None);
+        assert_eq!(identity.tag_line, None);
+    }
+
     #[test]
     fn test_shard_from_clid() {
         assert_eq!(client::shard_from_clid("ap1"), "ap");
         assert_eq!(client::shard_from_clid("na1"), "na");
         assert_eq!(client::shard_from_clid("eu3"), "eu");
         assert_eq!(client::shard_from_clid("kr"), "kr");
+        assert_eq!(client::shard_from_clid("br1"), "na");
+        assert_eq!(client::shard_from_clid("la2"), "na");
         assert_eq!(client::shard_from_clid(""), "");
     }
 
+    #[test]
+    fn test_region_from_clid() {
+        assert_eq!(region_from_clid("eu1"), "eu");
+        assert_eq!(region_from_clid("br1"), "br");
+        assert_eq!(region_from_clid("la1"), "latam");
+        assert_eq!(region_from_clid("ap"), "ap");
+    }
+
     /// Parse RiotGamesPrivateSettings.yaml and extract all cookies.
     fn parse_yaml_cookies(path: &str) -> RiotCookies {
         let content = std::fs::read_to_string(path)
@@ -133,7 +307,7 @@ mod tests {
         let shard = cookies.clid.as_deref().map(client::shard_from_clid).unwrap_or("ap");
         println!("  shard (derived): {}", shard);
 
-        let result = fetch_storefront(cookies).await;
+        let result = fetch_storefront_in_region(cookies, None).await;
         assert!(result.is_ok(), "Storefront fetch failed: {:?}", result.unwrap_err());
 
         let (sf, updated_cookies) = result.unwrap();
@@ -0,0 +1,23 @@
+/// Seconds in a day; the daily shop rotates at 00:00 UTC.
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Seconds from `now` (UNIX seconds) until the next daily shop rotation.
+///
+/// Exactly at midnight this returns a full day, since the rotation just happened.
+pub fn secs_until_daily_reset(now: u64) -> u64 {
+    SECS_PER_DAY - now % SECS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secs_until_daily_reset() {
+        // 2024-01-01 00:00:00 UTC
+        let midnight = 1_704_067_200;
+        assert_eq!(secs_until_daily_reset(midnight), SECS_PER_DAY);
+        assert_eq!(secs_until_daily_reset(midnight + 1), SECS_PER_DAY - 1);
+        assert_eq!(secs_until_daily_reset(midnight + SECS_PER_DAY - 1), 1);
+    }
+}
@@ -0,0 +1,45 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    exp: Option<i64>,
+}
+
+/// Read the `exp` claim (Unix seconds) from the payload of an ssid JWT.
+///
+/// The signature is not verified; this is only used to tell the user when the
+/// stored session will stop working. Returns `None` for anything that is not
+/// a JWT with a numeric `exp`.
+pub fn ssid_expiry(ssid: &str) -> Option<i64> {
+    let payload = ssid.split('.').nth(1)?;
+    let bytes = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice::<JwtClaims>(&bytes).ok()?.exp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwt_with_payload(payload: &str) -> String {
+        format!(
+            "eyJhbGciOiJSUzI1NiJ9.{}.signature",
+            general_purpose::URL_SAFE_NO_PAD.encode(payload)
+        )
+    }
+
+    #[test]
+    fn test_ssid_expiry() {
+        let ssid = jwt_with_payload(r#"{"sub":"abc","exp":1735689600}"#);
+        assert_eq!(ssid_expiry(&ssid), Some(1_735_689_600));
+    }
+
+    #[test]
+    fn test_ssid_expiry_invalid() {
+        assert_eq!(ssid_expiry("not-a-jwt"), None);
+        assert_eq!(ssid_expiry("a.!!!.c"), None);
+        assert_eq!(ssid_expiry(&jwt_with_payload(r#"{"sub":"abc"}"#)), None);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use super::types::{ApiStorefront, Bundle, BundleItem, DailyOffer, NightMarketOffer, Storefront};
+use super::types::{
+    AccessoryOffer, ApiStorefront, Bundle, BundleItem, DailyOffer, NightMarketOffer, Storefront,
+};
 
 // Known ItemTypeID values from the Valorant storefront API.
 // These are used on the frontend to dispatch item lookups to the correct DB table.
@@ -115,12 +117,46 @@ pub(super) fn parse_storefront(
             .collect()
     });
 
+    let accessory_remaining_secs = raw
+        .accessory_store
+        .as_ref()
+        .and_then(|store| store.remaining_duration_secs);
+
+    let accessory_store = raw.accessory_store.map(|store| {
+        store
+            .accessory_store_offers
+            .into_iter()
+            .map(|o| {
+                let (currency_id, cost) = o
+                    .offer
+                    .cost
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                // Offers without a reward entry fall back to the offer id, which
+                // matches the item UUID for single-item offers.
+                let (item_uuid, item_type_id) = match o.offer.rewards.into_iter().next() {
+                    Some(reward) => (reward.item_id, reward.item_type_id),
+                    None => (o.offer.offer_id, String::new()),
+                };
+                AccessoryOffer { item_uuid, item_type_id, cost, currency_id }
+            })
+            .collect()
+    });
+
+    let now = chrono::Utc::now().timestamp();
+    let daily_remaining_secs = raw.skins_panel_layout.remaining_duration_secs;
+
     Storefront {
         daily_offers,
-        daily_remaining_secs: raw.skins_panel_layout.remaining_duration_secs,
+        daily_remaining_secs,
         bundles,
         night_market,
         night_market_remaining_secs,
+        daily_expires_at: now + daily_remaining_secs as i64,
+        night_market_expires_at: night_market_remaining_secs.map(|secs| now + secs as i64),
+        accessory_store,
+        accessory_remaining_secs,
     }
 }
 
@@ -191,10 +227,14 @@ mod tests {
             },
             bonus_store: None,
             featured_bundle: None,
+            accessory_store: None,
         };
 
+        let before = chrono::Utc::now().timestamp();
         let sf = parse_storefront(raw, HashMap::new());
         assert_eq!(sf.daily_remaining_secs, 86400);
+        assert!(sf.daily_expires_at >= before + 86400);
+        assert_eq!(sf.night_market_expires_at, None);
         assert_eq!(sf.daily_offers.len(), 2);
         assert_eq!(sf.daily_offers[0], DailyOffer { skin_uuid: "skin-a".to_string(), vp_cost: 1775 });
         assert_eq!(sf.daily_offers[1], DailyOffer { skin_uuid: "skin-b".to_string(), vp_cost: 2175 });
@@ -212,6 +252,7 @@ mod tests {
             },
             bonus_store: None,
             featured_bundle: None,
+            accessory_store: None,
         };
         assert_eq!(parse_storefront(raw, HashMap::new()).daily_offers[0].vp_cost, 0);
     }
@@ -233,6 +274,7 @@ mod tests {
                 discount_costs: vp_cost_map(1305),
             }])),
             featured_bundle: None,
+            accessory_store: None,
         };
 
         let nm = parse_storefront(raw, HashMap::new()).night_market.unwrap();
@@ -255,6 +297,7 @@ mod tests {
             },
             bonus_store: None,
             featured_bundle: None,
+            accessory_store: None,
         };
         assert!(parse_storefront(raw, HashMap::new()).night_market.is_none());
     }
@@ -308,6 +351,7 @@ mod tests {
                     duration_remaining_secs: 259200,
                 }],
             }),
+            accessory_store: None,
         };
 
         let mut names = HashMap::new();
@@ -358,10 +402,116 @@ mod tests {
                     duration_remaining_secs: 3600,
                 }],
             }),
+            accessory_store: None,
         };
 
         let sf = parse_storefront(raw, HashMap::new());
         let bundles = sf.bundles.unwrap();
         assert_eq!(bundles[0].name, "unknown-uuid");
     }
+
+    #[test]
+    fn test_parse_v1_storefront() {
+        use super::super::types::ApiStorefrontV1;
+
+        let json = r#"{
+            "FeaturedBundle": {
+                "Bundle": {
+                    "ID": "bundle-id",
+                    "DataAssetID": "bundle-asset-uuid",
+                    "CurrencyID": "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741",
+                    "Items": [
+                        {
+                            "Item": {"ItemTypeID": "e7c63390-eda7-46e0-bb7a-a6abdacd2433", "ItemID": "skin-uuid", "Amount": 1},
+                            "BasePrice": 1775,
+                            "CurrencyID": "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741",
+                            "DiscountPercent": 0.5,
+                            "DiscountedPrice": 887,
+                            "IsPromoItem": false
+                        },
+                        {
+                            "Item": {"ItemTypeID": "d5f120f8-ff8c-4aac-92ea-f2b5acbe9475", "ItemID": "spray-uuid", "Amount": 1},
+                            "BasePrice": 325,
+                            "CurrencyID": "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741",
+                            "DiscountPercent": 0.0,
+                            "DiscountedPrice": 325,
+                            "IsPromoItem": false
+                        }
+                    ],
+                    "DurationRemainingInSeconds": 86400,
+                    "WholesaleOnly": false
+                },
+                "BundleRemainingDurationInSeconds": 86400
+            },
+            "SkinsPanelLayout": {
+                "SingleItemOffers": ["offer-1", "offer-2", "offer-3", "offer-4"],
+                "SingleItemOffersRemainingDurationInSeconds": 3600
+            }
+        }"#;
+
+        let v1: ApiStorefrontV1 = serde_json::from_str(json).unwrap();
+        let sf = parse_storefront(v1.into(), HashMap::new());
+
+        assert_eq!(sf.daily_offers.len(), 4);
+        assert_eq!(sf.daily_remaining_secs, 3600);
+        assert!(sf.night_market.is_none());
+
+        let bundles = sf.bundles.unwrap();
+        assert_eq!(bundles.len(), 1);
+        let bundle = &bundles[0];
+        assert_eq!(bundle.name, "bundle-asset-uuid");
+        assert_eq!(bundle.total_base_cost, 2100);
+        assert_eq!(bundle.total_discounted_cost, 1212);
+        assert!((bundle.total_discount_percent - 42.29).abs() < 0.01);
+        assert_eq!(bundle.bundle_remaining_secs, 86400);
+        assert_eq!(bundle.items.len(), 2);
+        assert_eq!(bundle.items[1].item_type_id, ITEM_TYPE_SPRAY);
+    }
+
+    #[test]
+    fn test_parse_accessory_store() {
+        let json = r#"{
+            "SkinsPanelLayout": {
+                "SingleItemOffers": [],
+                "SingleItemOffersRemainingDurationInSeconds": 0
+            },
+            "AccessoryStore": {
+                "AccessoryStoreOffers": [
+                    {
+                        "Offer": {
+                            "OfferID": "offer-1",
+                            "IsDirectPurchase": true,
+                            "Cost": {"85ca954a-41f2-ce94-9b45-8ca3dd39a00d": 4000},
+                            "Rewards": [
+                                {"ItemTypeID": "d5f120f8-ff8c-4aac-92ea-f2b5acbe9475", "ItemID": "spray-uuid", "Quantity": 1}
+                            ]
+                        },
+                        "ContractID": "contract-id"
+                    },
+                    {
+                        "Offer": {
+                            "OfferID": "offer-2",
+                            "Cost": {"85ca954a-41f2-ce94-9b45-8ca3dd39a00d": 2000}
+                        }
+                    }
+                ],
+                "AccessoryStoreRemainingDurationInSeconds": 302400
+            }
+        }"#;
+
+        let raw: ApiStorefront = serde_json::from_str(json).unwrap();
+        let sf = parse_storefront(raw, HashMap::new());
+
+        assert_eq!(sf.accessory_remaining_secs, Some(302400));
+        let offers = sf.accessory_store.unwrap();
+        assert_eq!(offers.len(), 2);
+        assert_eq!(offers[0], AccessoryOffer {
+            item_uuid: "spray-uuid".to_string(),
+            item_type_id: ITEM_TYPE_SPRAY.to_string(),
+            cost: 4000,
+            currency_id: "85ca954a-41f2-ce94-9b45-8ca3dd39a00d".to_string(),
+        });
+        assert_eq!(offers[1].item_uuid, "offer-2");
+        assert_eq!(offers[1].item_type_id, "");
+    }
 }
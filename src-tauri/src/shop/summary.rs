@@ -0,0 +1,201 @@
+use serde::Serialize;
+
+use super::types::{NightMarketOffer, Storefront};
+
+/// Aggregate VP savings across the night market offers.
+///
+/// `average_discount_percent` uses the same 0–100 convention as `NightMarketOffer`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NightMarketSavings {
+    pub total_base_cost: u64,
+    pub total_discounted_cost: u64,
+    pub total_saved: u64,
+    pub average_discount_percent: f64,
+}
+
+/// Sum the base and discounted costs of the night market offers.
+///
+/// Returns `None` when there are no offers to aggregate.
+pub fn night_market_savings(offers: &[NightMarketOffer]) -> Option<NightMarketSavings> {
+    if offers.is_empty() {
+        return None;
+    }
+
+    let total_base_cost: u64 = offers.iter().map(|o| o.base_cost).sum();
+    let total_discounted_cost: u64 = offers.iter().map(|o| o.discount_cost).sum();
+    let average_discount_percent =
+        offers.iter().map(|o| o.discount_percent).sum::<f64>() / offers.len() as f64;
+
+    Some(NightMarketSavings {
+        total_base_cost,
+        total_discounted_cost,
+        total_saved: total_base_cost.saturating_sub(total_discounted_cost),
+        average_discount_percent,
+    })
+}
+
+/// Zero every price in the storefront, leaving the offers themselves intact.
+///
+/// Discount percentages are kept since they carry no price information on their own.
+pub fn hide_costs(storefront: &mut Storefront) {
+    for offer in &mut storefront.daily_offers {
+        offer.vp_cost = 0;
+    }
+
+    for offer in storefront.night_market.iter_mut().flatten() {
+        offer.base_cost = 0;
+        offer.discount_cost = 0;
+    }
+
+    for bundle in storefront.bundles.iter_mut().flatten() {
+        bundle.total_base_cost = 0;
+        bundle.total_discounted_cost = 0;
+        for item in &mut bundle.items {
+            item.base_cost = 0;
+            item.discounted_cost = 0;
+        }
+    }
+
+    for offer in storefront.accessory_store.iter_mut().flatten() {
+        offer.cost = 0;
+    }
+}
+
+/// Remaining seconds for one featured bundle.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BundleTimer {
+    pub name: String,
+    pub remaining_secs: u64,
+}
+
+/// Every store timer in a storefront; `None` for stores that are not active.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StoreTimers {
+    pub daily_remaining_secs: u64,
+    pub night_market_remaining_secs: Option<u64>,
+    pub accessory_remaining_secs: Option<u64>,
+    pub bundles: Vec<BundleTimer>,
+}
+
+/// Collect the reset timers of every store in `storefront`.
+pub fn store_timers(storefront: &Storefront) -> StoreTimers {
+    StoreTimers {
+        daily_remaining_secs: storefront.daily_remaining_secs,
+        night_market_remaining_secs: storefront
+            .night_market
+            .as_ref()
+            .and(storefront.night_market_remaining_secs),
+        accessory_remaining_secs: storefront
+            .accessory_store
+            .as_ref()
+            .and(storefront.accessory_remaining_secs),
+        bundles: storefront
+            .bundles
+            .iter()
+            .flatten()
+            .map(|b| BundleTimer {
+                name: b.name.clone(),
+                remaining_secs: b.bundle_remaining_secs,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shop::types::{Bundle, BundleItem, DailyOffer};
+
+    fn offer(base_cost: u64, discount_cost: u64, discount_percent: f64) -> NightMarketOffer {
+        NightMarketOffer {
+            skin_uuid: "skin".to_string(),
+            base_cost,
+            discount_cost,
+            discount_percent,
+        }
+    }
+
+    #[test]
+    fn test_night_market_savings_totals() {
+        let offers = vec![offer(2175, 1305, 40.0), offer(1775, 1420, 20.0)];
+        let savings = night_market_savings(&offers).unwrap();
+        assert_eq!(savings.total_base_cost, 3950);
+        assert_eq!(savings.total_discounted_cost, 2725);
+        assert_eq!(savings.total_saved, 1225);
+        assert!((savings.average_discount_percent - 30.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_night_market_savings_empty() {
+        assert!(night_market_savings(&[]).is_none());
+    }
+
+    #[test]
+    fn test_hide_costs_zeroes_all_prices() {
+        let mut storefront = Storefront {
+            daily_offers: vec![DailyOffer { skin_uuid: "a".to_string(), vp_cost: 1775 }],
+            daily_remaining_secs: 60,
+            bundles: Some(vec![Bundle {
+                name: "Bundle".to_string(),
+                total_base_cost: 7100,
+                total_discounted_cost: 5325,
+                total_discount_percent: 25.0,
+                bundle_remaining_secs: 60,
+                items: vec![BundleItem {
+                    item_uuid: "b".to_string(),
+                    item_type_id: "type".to_string(),
+                    base_cost: 1775,
+                    discounted_cost: 1331,
+                    discount_percent: 25.0,
+                }],
+            }]),
+            night_market: Some(vec![offer(2175, 1305, 40.0)]),
+            night_market_remaining_secs: Some(60),
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        };
+
+        hide_costs(&mut storefront);
+
+        assert_eq!(storefront.daily_offers[0].vp_cost, 0);
+        let night = &storefront.night_market.as_ref().unwrap()[0];
+        assert_eq!((night.base_cost, night.discount_cost), (0, 0));
+        assert_eq!(night.discount_percent, 40.0);
+        let bundle = &storefront.bundles.as_ref().unwrap()[0];
+        assert_eq!((bundle.total_base_cost, bundle.total_discounted_cost), (0, 0));
+        assert_eq!((bundle.items[0].base_cost, bundle.items[0].discounted_cost), (0, 0));
+    }
+
+    #[test]
+    fn test_store_timers() {
+        let storefront = Storefront {
+            daily_offers: vec![],
+            daily_remaining_secs: 3600,
+            bundles: Some(vec![Bundle {
+                name: "Bundle".to_string(),
+                total_base_cost: 0,
+                total_discounted_cost: 0,
+                total_discount_percent: 0.0,
+                bundle_remaining_secs: 86400,
+                items: vec![],
+            }]),
+            night_market: None,
+            night_market_remaining_secs: Some(60),
+            daily_expires_at: 0,
+            night_market_expires_at: None,
+            accessory_store: None,
+            accessory_remaining_secs: None,
+        };
+
+        let timers = store_timers(&storefront);
+        assert_eq!(timers.daily_remaining_secs, 3600);
+        assert_eq!(timers.night_market_remaining_secs, None);
+        assert_eq!(timers.accessory_remaining_secs, None);
+        assert_eq!(
+            timers.bundles,
+            vec![BundleTimer { name: "Bundle".to_string(), remaining_secs: 86400 }]
+        );
+    }
+}
@@ -65,6 +65,17 @@ pub struct Bundle {
     pub items: Vec<BundleItem>,
 }
 
+/// A single item in the accessory store (sprays, cards, buddies, titles).
+///
+/// Accessories are not priced in VP, so the currency is kept next to `cost`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessoryOffer {
+    pub item_uuid: String,
+    pub item_type_id: String,
+    pub cost: u64,
+    pub currency_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Storefront {
     pub daily_offers: Vec<DailyOffer>,
@@ -72,6 +83,39 @@ pub struct Storefront {
     pub bundles: Option<Vec<Bundle>>,
     pub night_market: Option<Vec<NightMarketOffer>>,
     pub night_market_remaining_secs: Option<u64>,
+    /// UNIX seconds when the daily offers rotate; unlike `daily_remaining_secs`
+    /// this stays correct however long after the fetch it is read.
+    #[serde(default)]
+    pub daily_expires_at: i64,
+    /// UNIX seconds when the night market closes.
+    #[serde(default)]
+    pub night_market_expires_at: Option<i64>,
+    // Caches and history written before the accessory store was parsed lack these.
+    #[serde(default)]
+    pub accessory_store: Option<Vec<AccessoryOffer>>,
+    #[serde(default)]
+    pub accessory_remaining_secs: Option<u64>,
+}
+
+/// Expiry information for one account's `storefront_cache` row.
+///
+/// Timestamps are absolute UNIX seconds as stored in the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedShopInfo {
+    pub account_id: i64,
+    pub expires_at: i64,
+    pub nm_expires_at: Option<i64>,
+    pub is_valid: bool,
+}
+
+/// The Riot ID an account is actually logged in as, read from the userinfo endpoint.
+///
+/// `game_name` and `tag_line` are `None` when the token carries no `acct` claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RiotIdentity {
+    pub puuid: String,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
 }
 
 // -- Internal API response types ----------------------------------------------
@@ -84,6 +128,8 @@ pub(super) struct ApiStorefront {
     pub(super) bonus_store: Option<BonusStoreData>,
     #[serde(rename = "FeaturedBundle")]
     pub(super) featured_bundle: Option<FeaturedBundleWrapper>,
+    #[serde(rename = "AccessoryStore")]
+    pub(super) accessory_store: Option<AccessoryStoreData>,
 }
 
 #[derive(Deserialize)]
@@ -111,6 +157,74 @@ pub(super) struct ApiBundleData {
     pub(super) duration_remaining_secs: u64,
 }
 
+/// VP currency UUID, used to key bundle totals derived from v1 responses.
+const VP_CURRENCY_ID: &str = "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741";
+
+/// Response shape of the legacy `store/v1/storefront` endpoint.
+///
+/// v1 features a single bundle (`FeaturedBundle.Bundle`) rather than a list,
+/// and omits the bundle totals, so it is converted into [`ApiStorefront`]
+/// before parsing.
+#[derive(Deserialize)]
+pub(super) struct ApiStorefrontV1 {
+    #[serde(rename = "SkinsPanelLayout")]
+    pub(super) skins_panel_layout: SkinsPanelLayout,
+    #[serde(rename = "BonusStore")]
+    pub(super) bonus_store: Option<BonusStoreData>,
+    #[serde(rename = "FeaturedBundle")]
+    pub(super) featured_bundle: Option<FeaturedBundleV1>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct FeaturedBundleV1 {
+    #[serde(rename = "Bundle")]
+    pub(super) bundle: ApiBundleDataV1,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ApiBundleDataV1 {
+    #[serde(rename = "DataAssetID")]
+    pub(super) data_asset_id: String,
+    #[serde(rename = "Items")]
+    pub(super) items: Vec<ApiBundleItem>,
+    #[serde(rename = "DurationRemainingInSeconds")]
+    pub(super) duration_remaining_secs: u64,
+}
+
+impl From<ApiBundleDataV1> for ApiBundleData {
+    fn from(v1: ApiBundleDataV1) -> Self {
+        let base: u64 = v1.items.iter().map(|i| i.base_price).sum();
+        let discounted: u64 = v1.items.iter().map(|i| i.discounted_price).sum();
+        let total_discount_percent = if base > 0 {
+            1.0 - discounted as f64 / base as f64
+        } else {
+            0.0
+        };
+
+        ApiBundleData {
+            data_asset_id: v1.data_asset_id,
+            items: v1.items,
+            total_base_cost: Some(HashMap::from([(VP_CURRENCY_ID.to_string(), base)])),
+            total_discounted_cost: Some(HashMap::from([(VP_CURRENCY_ID.to_string(), discounted)])),
+            total_discount_percent,
+            duration_remaining_secs: v1.duration_remaining_secs,
+        }
+    }
+}
+
+impl From<ApiStorefrontV1> for ApiStorefront {
+    fn from(v1: ApiStorefrontV1) -> Self {
+        ApiStorefront {
+            skins_panel_layout: v1.skins_panel_layout,
+            bonus_store: v1.bonus_store,
+            featured_bundle: v1.featured_bundle.map(|fb| FeaturedBundleWrapper {
+                bundles: vec![fb.bundle.into()],
+            }),
+            accessory_store: None,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub(super) struct ApiBundleItem {
     #[serde(rename = "Item")]
@@ -177,6 +291,31 @@ pub(super) struct BonusOffer {
     pub(super) cost: HashMap<String, u64>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct AccessoryStoreData {
+    #[serde(rename = "AccessoryStoreOffers")]
+    pub(super) accessory_store_offers: Vec<AccessoryStoreOffer>,
+    #[serde(rename = "AccessoryStoreRemainingDurationInSeconds")]
+    pub(super) remaining_duration_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AccessoryStoreOffer {
+    #[serde(rename = "Offer")]
+    pub(super) offer: AccessoryOfferData,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AccessoryOfferData {
+    #[serde(rename = "OfferID")]
+    pub(super) offer_id: String,
+    #[serde(rename = "Cost")]
+    pub(super) cost: HashMap<String, u64>,
+    /// Same `{ItemTypeID, ItemID}` shape as bundle items; usually a single entry.
+    #[serde(rename = "Rewards", default)]
+    pub(super) rewards: Vec<ApiBundleItemDetail>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct EntitlementsResponse {
     pub(super) entitlements_token: String,
@@ -185,4 +324,29 @@ pub(super) struct EntitlementsResponse {
 #[derive(Deserialize)]
 pub(super) struct UserInfoResponse {
     pub(super) sub: String,
+    /// Missing on older tokens.
+    #[serde(default)]
+    pub(super) acct: Option<UserInfoAccount>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct UserInfoAccount {
+    #[serde(default)]
+    pub(super) game_name: Option<String>,
+    #[serde(default)]
+    pub(super) tag_line: Option<String>,
+}
+
+impl From<UserInfoResponse> for RiotIdentity {
+    fn from(info: UserInfoResponse) -> Self {
+        let (game_name, tag_line) = match info.acct {
+            Some(acct) => (acct.game_name, acct.tag_line),
+            None => (None, None),
+        };
+        Self {
+            puuid: info.sub,
+            game_name,
+            tag_line,
+        }
+    }
 }
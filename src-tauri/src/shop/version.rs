@@ -62,6 +62,25 @@ pub(super) async fn fetch_version_info() -> Result<VersionInfo, ShopError> {
     })
 }
 
+/// Build version info around a caller-pinned client version.
+///
+/// The User-Agent still comes from valorant-api.com when reachable; otherwise a
+/// build-less User-Agent is used so the pinned version can still be tried.
+pub(super) async fn pinned_version_info(client_version: String) -> VersionInfo {
+    let user_agent = match fetch_version_info().await {
+        Ok(info) => info.user_agent,
+        Err(e) => {
+            log::warn!("Version API unavailable, using generic User-Agent: {}", e);
+            "RiotClient rso-auth (Windows;10;;Professional, x64)".to_string()
+        }
+    };
+
+    VersionInfo {
+        client_version,
+        user_agent,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
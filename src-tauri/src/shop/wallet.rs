@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+const VP_CURRENCY_ID: &str = "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741";
+const RADIANITE_CURRENCY_ID: &str = "e59aa87c-4cbf-517a-5983-6e81511be9b7";
+const KINGDOM_CREDITS_CURRENCY_ID: &str = "85ca954a-41f2-ce94-9b45-8ca3dd39a00d";
+
+/// The account's currency balances.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Wallet {
+    pub vp: u64,
+    pub radianite: u64,
+    pub kingdom_credits: u64,
+}
+
+// -- Internal API response types ----------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct ApiWallet {
+    #[serde(rename = "Balances", default)]
+    balances: HashMap<String, u64>,
+}
+
+/// Pick the known currencies out of a wallet response; missing ones are zero.
+pub(super) fn parse_wallet(raw: ApiWallet) -> Wallet {
+    let balance = |id: &str| raw.balances.get(id).copied().unwrap_or(0);
+    Wallet {
+        vp: balance(VP_CURRENCY_ID),
+        radianite: balance(RADIANITE_CURRENCY_ID),
+        kingdom_credits: balance(KINGDOM_CREDITS_CURRENCY_ID),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wallet() {
+        let json = r#"{
+            "Balances": {
+                "85ad13f7-3d1b-5128-9eb2-7cd8ee0b5741": 1250,
+                "e59aa87c-4cbf-517a-5983-6e81511be9b7": 40,
+                "85ca954a-41f2-ce94-9b45-8ca3dd39a00d": 8700,
+                "f08d4ae3-939c-4576-ab26-09ce1f23bb37": 3
+            }
+        }"#;
+
+        let raw: ApiWallet = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parse_wallet(raw),
+            Wallet {
+                vp: 1250,
+                radianite: 40,
+                kingdom_credits: 8700,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_wallet_missing_balances() {
+        let raw: ApiWallet = serde_json::from_str("{}").unwrap();
+        assert_eq!(parse_wallet(raw).vp, 0);
+    }
+}
@@ -1,10 +1,13 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+use serde::Serialize;
+
 use super::db;
 use super::error::SkinsError;
 use super::models::{
-    BuddiesApiResponse, ContentTiersApiResponse, FlexApiResponse, PlayercardsApiResponse,
-    SkinsApiResponse, SpraysApiResponse, VersionApiResponse,
+    AgentsApiResponse, BuddiesApiResponse, ContentTiersApiResponse, FlexApiResponse, PlayercardsApiResponse,
+    SkinsApiResponse, SpraysApiResponse, TitlesApiResponse, VersionApiResponse,
 };
 
 const CONTENT_TIERS_URL: &str = "https://valorant-api.com/v1/contenttiers";
@@ -13,8 +16,29 @@ const BUDDIES_URL: &str = "https://valorant-api.com/v1/buddies";
 const FLEX_URL: &str = "https://valorant-api.com/v1/flex";
 const PLAYERCARDS_URL: &str = "https://valorant-api.com/v1/playercards";
 const SPRAYS_URL: &str = "https://valorant-api.com/v1/sprays";
+const TITLES_URL: &str = "https://valorant-api.com/v1/playertitles";
+const AGENTS_URL: &str = "https://valorant-api.com/v1/agents?isPlayableCharacter=true";
 const VERSION_URL: &str = "https://valorant-api.com/v1/version";
 
+/// Stored in place of a real version when tables were filled without one.
+/// Never matches a real version, so the next successful version fetch resyncs.
+const FALLBACK_VERSION: &str = "unknown";
+
+/// Set by `cancel_sync`, checked by `fill_tables` between stages.
+static SYNC_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Ask an in-progress sync to stop before its next stage.
+pub fn cancel_sync() {
+    log::info!("Skins sync cancellation requested");
+    SYNC_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Mark `report` as cancelled if `cancel_sync` was called, so the caller can stop.
+fn stop_if_cancelled(report: &mut SyncReport) -> bool {
+    report.cancelled = SYNC_CANCELLED.load(Ordering::SeqCst);
+    report.cancelled
+}
+
 fn build_client() -> Result<reqwest::Client, SkinsError> {
     reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(5))
@@ -103,6 +127,32 @@ async fn fetch_sprays(client: &reqwest::Client) -> Result<SpraysApiResponse, Ski
     resp.json().await.map_err(SkinsError::from)
 }
 
+async fn fetch_titles(client: &reqwest::Client) -> Result<TitlesApiResponse, SkinsError> {
+    let resp = client.get(TITLES_URL).send().await?;
+
+    if !resp.status().is_success() {
+        return Err(SkinsError::ApiFailed(format!(
+            "playertitles returned status {}",
+            resp.status()
+        )));
+    }
+
+    resp.json().await.map_err(SkinsError::from)
+}
+
+async fn fetch_agents(client: &reqwest::Client) -> Result<AgentsApiResponse, SkinsError> {
+    let resp = client.get(AGENTS_URL).send().await?;
+
+    if !resp.status().is_success() {
+        return Err(SkinsError::ApiFailed(format!(
+            "agents returned status {}",
+            resp.status()
+        )));
+    }
+
+    resp.json().await.map_err(SkinsError::from)
+}
+
 async fn fetch_version(client: &reqwest::Client) -> Result<String, SkinsError> {
     let resp = client.get(VERSION_URL).send().await?;
 
@@ -117,20 +167,56 @@ async fn fetch_version(client: &reqwest::Client) -> Result<String, SkinsError> {
     Ok(api.data.version)
 }
 
+/// Sections written by a sync, as reported in [`SyncReport`].
+///
+/// A section fails on its own; the others are still written.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SyncReport {
+    pub updated: Vec<String>,
+    /// `(section, error)` for each section that could not be fetched or written.
+    pub failed: Vec<(String, String)>,
+    /// Set when `cancel_sync` stopped the sync; sections not listed were not attempted.
+    pub cancelled: bool,
+}
+
+impl SyncReport {
+    fn record(&mut self, section: &str, result: Result<usize, SkinsError>) {
+        match result {
+            Ok(count) => {
+                log::info!("Inserted/updated {} {}", count, section);
+                self.updated.push(section.to_string());
+            }
+            Err(e) => {
+                log::warn!("Failed to sync {}: {}", section, e);
+                self.failed.push((section.to_string(), e.to_string()));
+            }
+        }
+    }
+}
+
 /// Sync the skins database with valorant-api.com.
 ///
-/// Returns `Ok(true)` if new data was written, `Ok(false)` if already up to date.
-pub async fn sync_skins_database() -> Result<bool, SkinsError> {
+/// Returns an empty report when already up to date. The new version is only
+/// stored when every section succeeded, so a later sync retries the failed ones.
+pub async fn sync_skins_database() -> Result<SyncReport, SkinsError> {
+    SYNC_CANCELLED.store(false, Ordering::SeqCst);
     let client = build_client()?;
-    let remote_version = fetch_version(&client).await?;
+    let status = db::get_table_status()?;
+    let remote_version = resolve_sync_version(fetch_version(&client).await, status.any_empty())?;
+
+    // The real version is unknown, so only fill the gaps and never store the sentinel
+    if remote_version == FALLBACK_VERSION {
+        log::info!("Partial sync: filling empty tables without a known version");
+        return fill_tables(&client, &status, false).await;
+    }
+
     let stored_version = db::get_stored_version()?;
 
     let version_changed = stored_version.as_deref() != Some(&remote_version);
-    let status = db::get_table_status()?;
 
     if !version_changed && !status.any_empty() {
         log::info!("Skins database already up to date (version {})", remote_version);
-        return Ok(false);
+        return Ok(SyncReport::default());
     }
 
     if version_changed {
@@ -146,47 +232,212 @@ pub async fn sync_skins_database() -> Result<bool, SkinsError> {
         );
     }
 
+    let report = fill_tables(&client, &status, version_changed).await?;
+
+    // Version is only written after every section was inserted (retry-safe).
+    // Skip the write if version was already correct (partial sync for empty tables).
+    if report.cancelled {
+        log::info!("Skins sync cancelled; version left at {:?}", stored_version);
+    } else if version_changed && report.failed.is_empty() {
+        db::set_stored_version(&remote_version)?;
+        log::info!("Skins database synced to version {}", remote_version);
+    } else if !report.failed.is_empty() {
+        log::warn!(
+            "Skins sync incomplete, {} section(s) failed; version left at {:?}",
+            report.failed.len(),
+            stored_version
+        );
+    }
+
+    Ok(report)
+}
+
+/// Pick the version to sync against.
+///
+/// A failed version fetch only aborts the sync when every table already has
+/// data; otherwise `FALLBACK_VERSION` is returned and only the empty tables
+/// are filled, so a first run still gets skin data while the version endpoint
+/// is down.
+fn resolve_sync_version(
+    fetched: Result<String, SkinsError>,
+    any_empty: bool,
+) -> Result<String, SkinsError> {
+    match fetched {
+        Ok(version) => Ok(version),
+        Err(e) if any_empty => {
+            log::warn!(
+                "Version fetch failed ({}), syncing empty tables under fallback version",
+                e
+            );
+            Ok(FALLBACK_VERSION.to_string())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Re-fetch only the tables that are currently empty, leaving the stored version alone.
+///
+/// Returns an empty report when no table is empty.
+pub async fn sync_empty_tables() -> Result<SyncReport, SkinsError> {
+    SYNC_CANCELLED.store(false, Ordering::SeqCst);
+    let status = db::get_table_status()?;
+    if !status.any_empty() {
+        log::info!("Partial sync: no empty tables");
+        return Ok(SyncReport::default());
+    }
+
+    let client = build_client()?;
+    log::info!("Partial sync: filling empty tables on request");
+    fill_tables(&client, &status, false).await
+}
+
+/// Fetch and insert every table that is empty in `status`, or all of them when `all` is set.
+///
+/// Each table is committed as its own stage, so a cancelled sync keeps the
+/// stages it finished and a later partial sync only refills the rest. A
+/// failed stage is recorded in the report; cancellation returns the report so
+/// far with `cancelled` set.
+async fn fill_tables(
+    client: &reqwest::Client,
+    status: &db::TableStatus,
+    all: bool,
+) -> Result<SyncReport, SkinsError> {
+    let mut report = SyncReport::default();
+
     // Tiers are fetched together with weapons since they share a foreign key.
-    if version_changed || status.weapons_empty {
-        let tiers = fetch_content_tiers(&client).await?;
-        db::insert_tiers(&tiers.data)?;
-        log::info!("Synced {} content tiers", tiers.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.weapons_empty {
+        let result = async {
+            let tiers = fetch_content_tiers(client).await?;
+            db::insert_tiers(&tiers.data)?;
+            log::info!("Synced {} content tiers", tiers.data.len());
+
+            let skins = fetch_weapon_skins(client).await?;
+            db::insert_skins(&skins.data)?;
+            Ok::<_, SkinsError>(skins.data.len())
+        }
+        .await;
+        report.record("weapons", result);
+    }
 
-        let skins = fetch_weapon_skins(&client).await?;
-        db::insert_skins(&skins.data)?;
-        log::info!("Inserted/updated {} weapon skins", skins.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.buddies_empty {
+        let result = async {
+            let buddies = fetch_buddies(client).await?;
+            db::insert_buddies(&buddies.data)?;
+            Ok::<_, SkinsError>(buddies.data.len())
+        }
+        .await;
+        report.record("buddies", result);
     }
 
-    if version_changed || status.buddies_empty {
-        let buddies = fetch_buddies(&client).await?;
-        db::insert_buddies(&buddies.data)?;
-        log::info!("Inserted/updated {} buddies", buddies.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.flex_empty {
+        let result = async {
+            let flex = fetch_flex(client).await?;
+            db::insert_flex(&flex.data)?;
+            Ok::<_, SkinsError>(flex.data.len())
+        }
+        .await;
+        report.record("flex", result);
     }
 
-    if version_changed || status.flex_empty {
-        let flex = fetch_flex(&client).await?;
-        db::insert_flex(&flex.data)?;
-        log::info!("Inserted/updated {} flex items", flex.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.playercards_empty {
+        let result = async {
+            let playercards = fetch_playercards(client).await?;
+            db::insert_playercards(&playercards.data)?;
+            Ok::<_, SkinsError>(playercards.data.len())
+        }
+        .await;
+        report.record("playercards", result);
     }
 
-    if version_changed || status.playercards_empty {
-        let playercards = fetch_playercards(&client).await?;
-        db::insert_playercards(&playercards.data)?;
-        log::info!("Inserted/updated {} playercards", playercards.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.sprays_empty {
+        let result = async {
+            let sprays = fetch_sprays(client).await?;
+            db::insert_sprays(&sprays.data)?;
+            Ok::<_, SkinsError>(sprays.data.len())
+        }
+        .await;
+        report.record("sprays", result);
     }
 
-    if version_changed || status.sprays_empty {
-        let sprays = fetch_sprays(&client).await?;
-        db::insert_sprays(&sprays.data)?;
-        log::info!("Inserted/updated {} sprays", sprays.data.len());
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.titles_empty {
+        let result = async {
+            let titles = fetch_titles(client).await?;
+            db::insert_titles(&titles.data)?;
+            Ok::<_, SkinsError>(titles.data.len())
+        }
+        .await;
+        report.record("titles", result);
     }
 
-    // Version is only written after successful data insertion (retry-safe).
-    // Skip the write if version was already correct (partial sync for empty tables).
-    if version_changed {
-        db::set_stored_version(&remote_version)?;
-        log::info!("Skins database synced to version {}", remote_version);
+    if stop_if_cancelled(&mut report) {
+        return Ok(report);
+    }
+    if all || status.agents_empty {
+        let result = async {
+            let agents = fetch_agents(client).await?;
+            db::insert_agents(&agents.data)?;
+            Ok::<_, SkinsError>(agents.data.len())
+        }
+        .await;
+        report.record("agents", result);
     }
 
-    Ok(true)
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_fetch_failure_with_empty_tables_uses_fallback() {
+        let fetched = Err(SkinsError::ApiFailed("version returned status 503".to_string()));
+        let version = resolve_sync_version(fetched, true).unwrap();
+        assert_eq!(version, FALLBACK_VERSION);
+    }
+
+    #[test]
+    fn test_version_fetch_failure_with_full_tables_aborts() {
+        let fetched = Err(SkinsError::ApiFailed("version returned status 503".to_string()));
+        assert!(matches!(
+            resolve_sync_version(fetched, false),
+            Err(SkinsError::ApiFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_sync_report_records_sections_independently() {
+        let mut report = SyncReport::default();
+        report.record("buddies", Ok(12));
+        report.record("flex", Err(SkinsError::ApiFailed("flex returned status 500".to_string())));
+        report.record("sprays", Ok(3));
+
+        assert_eq!(report.updated, vec!["buddies".to_string(), "sprays".to_string()]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "flex");
+    }
+
+    #[test]
+    fn test_version_fetch_success_is_used() {
+        let fetched = Ok("09.07.00.2546215".to_string());
+        assert_eq!(resolve_sync_version(fetched, true).unwrap(), "09.07.00.2546215");
+    }
 }
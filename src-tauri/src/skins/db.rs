@@ -1,26 +1,50 @@
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
 
 use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
 
 use super::error::SkinsError;
 use super::models::{
-    BuddyApiEntry, BuddyItem, BuddyLevelApiEntry, ChromaApiEntry, ContentTierApiEntry,
-    FlexApiEntry, FlexItem, LevelApiEntry, PlayercardApiEntry, PlayercardItem, SkinApiEntry,
-    SkinWeapon, SprayApiEntry, SprayItem, SprayLevelApiEntry,
+    AgentApiEntry, AgentItem, BuddyApiEntry, BuddyItem, BuddyLevelApiEntry, ChromaApiEntry,
+    ContentTier, ContentTierApiEntry, FlexApiEntry, FlexItem, LevelApiEntry, PlayercardApiEntry,
+    PlayercardItem, ResolvedItem, SkinApiEntry, SkinWeapon, SprayApiEntry, SprayItem,
+    SprayLevelApiEntry, TitleApiEntry, TitleItem, VersionHistoryEntry,
 };
 
 const SCHEMA_SQL: &str = include_str!("schema.sql");
 
 static SKINS_DB_PATH: Mutex<Option<String>> = Mutex::new(None);
 
+/// Connection reused by every skins.db query, opened on first use.
+///
+/// Tagged with its path so a changed `SKINS_DB_PATH` reopens it.
+static SHARED_CONNECTION: Mutex<Option<(String, Connection)>> = Mutex::new(None);
+
+/// Number of connections `get_connection` has opened.
+static CONNECTIONS_OPENED: AtomicUsize = AtomicUsize::new(0);
+
+/// Exclusive handle to the shared skins.db connection; released on drop.
+pub(super) struct SkinsConnection(MutexGuard<'static, Option<(String, Connection)>>);
+
+impl Deref for SkinsConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        // `get_connection` only hands out a guard after filling the slot
+        &self.0.as_ref().expect("skins connection not open").1
+    }
+}
+
+/// Close the shared connection so the next query reopens it.
+fn close_shared_connection() {
+    *SHARED_CONNECTION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
 fn get_default_skins_db_path() -> Result<PathBuf, String> {
-    let exe_path = std::env::current_exe()
-        .map_err(|e| format!("Failed to get executable path: {}", e))?;
-    let exe_dir = exe_path
-        .parent()
-        .ok_or("Failed to get executable directory")?;
-    Ok(exe_dir.join("skins.db"))
+    Ok(crate::db::init::get_db_dir()?.join("skins.db"))
 }
 
 pub fn initialize_skins_db(db_path: Option<PathBuf>) -> Result<(), String> {
@@ -28,6 +52,7 @@ pub fn initialize_skins_db(db_path: Option<PathBuf>) -> Result<(), String> {
     let path = db_path.unwrap_or(default_path);
     let path_str = path.to_string_lossy().to_string();
     *SKINS_DB_PATH.lock().unwrap_or_else(|e| e.into_inner()) = Some(path_str.clone());
+    close_shared_connection();
 
     let conn = Connection::open(&path)
         .map_err(|e| format!("Failed to open skins database: {}", e))?;
@@ -35,17 +60,62 @@ pub fn initialize_skins_db(db_path: Option<PathBuf>) -> Result<(), String> {
     conn.execute_batch(SCHEMA_SQL)
         .map_err(|e| format!("Failed to initialize skins schema: {}", e))?;
 
+    // Existing tier rows have no name; clear the version so the next sync refetches them
+    if conn
+        .execute("ALTER TABLE tiers ADD COLUMN displayName TEXT", [])
+        .is_ok()
+    {
+        let _ = conn.execute("UPDATE info SET version = NULL WHERE rowid = 1", []);
+    }
+
     Ok(())
 }
 
-pub(super) fn get_connection() -> Result<Connection, SkinsError> {
+/// Move skins.db into `new_dir`, mirroring `db::init::move_database`.
+pub fn move_skins_db(new_dir: &Path) -> Result<PathBuf, String> {
+    let mut db_path = SKINS_DB_PATH.lock().unwrap_or_else(|e| e.into_inner());
+    let current = PathBuf::from(db_path.clone().ok_or("Skins DB not initialized")?);
+    let target = new_dir.join("skins.db");
+
+    if current == target {
+        return Ok(target);
+    }
+
+    // The old file can't be removed on Windows while it is still open
+    close_shared_connection();
+    crate::db::init::copy_sqlite_database(&current, &target)?;
+    *db_path = Some(target.to_string_lossy().to_string());
+
+    if let Err(e) = std::fs::remove_file(&current) {
+        log::warn!("Failed to remove old skins database {}: {}", current.display(), e);
+    }
+
+    log::info!("Moved skins database: {} -> {}", current.display(), target.display());
+    Ok(target)
+}
+
+/// Lock the shared skins.db connection, opening it if needed.
+///
+/// Queries run one at a time; never call this while already holding the handle.
+pub(super) fn get_connection() -> Result<SkinsConnection, SkinsError> {
     let path = SKINS_DB_PATH
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .clone()
         .ok_or_else(|| SkinsError::Database("Skins DB not initialized".to_string()))?;
 
-    Connection::open(&path).map_err(SkinsError::from)
+    let mut shared = SHARED_CONNECTION.lock().unwrap_or_else(|e| e.into_inner());
+    let stale = match shared.as_ref() {
+        Some((open_path, _)) => *open_path != path,
+        None => true,
+    };
+    if stale {
+        let conn = Connection::open(&path).map_err(SkinsError::from)?;
+        CONNECTIONS_OPENED.fetch_add(1, Ordering::Relaxed);
+        *shared = Some((path, conn));
+    }
+
+    Ok(SkinsConnection(shared))
 }
 
 pub(super) fn get_stored_version() -> Result<Option<String>, SkinsError> {
@@ -58,12 +128,16 @@ pub(super) fn get_stored_version() -> Result<Option<String>, SkinsError> {
     Ok(version)
 }
 
-pub(super) struct TableStatus {
+/// Which skins-DB tables have no rows, e.g. after an interrupted sync.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableStatus {
     pub weapons_empty: bool,
     pub buddies_empty: bool,
     pub flex_empty: bool,
     pub playercards_empty: bool,
     pub sprays_empty: bool,
+    pub titles_empty: bool,
+    pub agents_empty: bool,
 }
 
 impl TableStatus {
@@ -73,6 +147,8 @@ impl TableStatus {
             || self.flex_empty
             || self.playercards_empty
             || self.sprays_empty
+            || self.titles_empty
+            || self.agents_empty
     }
 }
 
@@ -87,7 +163,7 @@ fn is_table_empty(conn: &Connection, table: &str) -> Result<bool, SkinsError> {
     Ok(count == 0)
 }
 
-pub(super) fn get_table_status() -> Result<TableStatus, SkinsError> {
+pub fn get_table_status() -> Result<TableStatus, SkinsError> {
     let conn = get_connection()?;
     Ok(TableStatus {
         weapons_empty: is_table_empty(&conn, "weapons")?,
@@ -95,23 +171,54 @@ pub(super) fn get_table_status() -> Result<TableStatus, SkinsError> {
         flex_empty: is_table_empty(&conn, "flex")?,
         playercards_empty: is_table_empty(&conn, "playercards")?,
         sprays_empty: is_table_empty(&conn, "sprays")?,
+        titles_empty: is_table_empty(&conn, "titles")?,
+        agents_empty: is_table_empty(&conn, "agents")?,
     })
 }
 
+/// Record `version` as current and append it to `version_history`.
 pub(super) fn set_stored_version(version: &str) -> Result<(), SkinsError> {
     let conn = get_connection()?;
-    conn.execute(
+    let tx = conn.unchecked_transaction().map_err(SkinsError::from)?;
+    tx.execute(
         "UPDATE info SET version = ?1 WHERE rowid = 1",
         [version],
     )
     .map_err(SkinsError::from)?;
+    tx.execute(
+        "INSERT INTO version_history (version, synced_at) VALUES (?1, ?2)",
+        (version, chrono::Utc::now().timestamp()),
+    )
+    .map_err(SkinsError::from)?;
+    tx.commit().map_err(SkinsError::from)?;
     Ok(())
 }
 
+/// Every version the database has been synced to, newest first.
+pub fn get_version_history() -> Result<Vec<VersionHistoryEntry>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT version, synced_at FROM version_history ORDER BY id DESC")
+        .map_err(SkinsError::from)?;
+
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(VersionHistoryEntry {
+                version: row.get(0)?,
+                synced_at: row.get(1)?,
+            })
+        })
+        .map_err(SkinsError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(SkinsError::from)?;
+
+    Ok(entries)
+}
+
 pub(super) fn insert_tiers(tiers: &[ContentTierApiEntry]) -> Result<(), SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare("INSERT OR REPLACE INTO tiers (uuid, color, rank, displayIcon) VALUES (?1, ?2, ?3, ?4)")
+        .prepare("INSERT OR REPLACE INTO tiers (uuid, color, rank, displayIcon, displayName) VALUES (?1, ?2, ?3, ?4, ?5)")
         .map_err(SkinsError::from)?;
 
     for tier in tiers {
@@ -120,6 +227,7 @@ pub(super) fn insert_tiers(tiers: &[ContentTierApiEntry]) -> Result<(), SkinsErr
             &tier.highlight_color,
             tier.rank,
             &tier.display_icon,
+            &tier.display_name,
         ))
         .map_err(SkinsError::from)?;
     }
@@ -208,12 +316,36 @@ fn map_skin_weapon_row(row: &rusqlite::Row) -> rusqlite::Result<SkinWeapon> {
         tier_color: row.get(4)?,
         tier_rank: row.get(5)?,
         tier_icon: row.get(6)?,
+        tier_name: row.get(7)?,
     })
 }
 
+pub fn get_all_content_tiers() -> Result<Vec<ContentTier>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT uuid, displayName, rank, color, displayIcon FROM tiers ORDER BY rank")
+        .map_err(SkinsError::from)?;
+
+    let tiers = stmt
+        .query_map([], |row| {
+            Ok(ContentTier {
+                uuid: row.get(0)?,
+                display_name: row.get(1)?,
+                rank: row.get(2)?,
+                color: row.get(3)?,
+                display_icon: row.get(4)?,
+            })
+        })
+        .map_err(SkinsError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(SkinsError::from)?;
+
+    Ok(tiers)
+}
+
 const LEVEL_LOOKUP_SQL: &str =
     "SELECT w.uuid, w.displayName, w.displayIcon, w.tierUuid,
-            t.color, t.rank, t.displayIcon
+            t.color, t.rank, t.displayIcon, t.displayName
      FROM levels l
      JOIN weapons w ON l.weaponUuid = w.uuid
      LEFT JOIN tiers t ON w.tierUuid = t.uuid
@@ -221,7 +353,7 @@ const LEVEL_LOOKUP_SQL: &str =
 
 pub fn get_skin_by_level_uuid(level_uuid: &str) -> Result<Option<SkinWeapon>, SkinsError> {
     let conn = get_connection()?;
-    let mut stmt = conn.prepare(LEVEL_LOOKUP_SQL).map_err(SkinsError::from)?;
+    let mut stmt = conn.prepare_cached(LEVEL_LOOKUP_SQL).map_err(SkinsError::from)?;
 
     let result = stmt
         .query_row([level_uuid], map_skin_weapon_row)
@@ -231,11 +363,43 @@ pub fn get_skin_by_level_uuid(level_uuid: &str) -> Result<Option<SkinWeapon>, Sk
     Ok(result)
 }
 
+const WEAPON_LOOKUP_SQL: &str =
+    "SELECT w.uuid, w.displayName, w.displayIcon, w.tierUuid,
+            t.color, t.rank, t.displayIcon, t.displayName
+     FROM weapons w
+     LEFT JOIN tiers t ON w.tierUuid = t.uuid
+     WHERE w.uuid = ?1";
+
+pub fn get_skin_by_uuid(weapon_uuid: &str) -> Result<Option<SkinWeapon>, SkinsError> {
+    let conn = get_connection()?;
+
+    let result = conn
+        .query_row(WEAPON_LOOKUP_SQL, [weapon_uuid], map_skin_weapon_row)
+        .optional()
+        .map_err(SkinsError::from)?;
+
+    Ok(result)
+}
+
+pub fn get_skins_by_uuids(weapon_uuids: &[String]) -> Result<Vec<Option<SkinWeapon>>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare_cached(WEAPON_LOOKUP_SQL).map_err(SkinsError::from)?;
+
+    weapon_uuids
+        .iter()
+        .map(|uuid| {
+            stmt.query_row([uuid.as_str()], map_skin_weapon_row)
+                .optional()
+                .map_err(SkinsError::from)
+        })
+        .collect()
+}
+
 pub fn get_skins_by_level_uuids(
     level_uuids: &[String],
 ) -> Result<Vec<Option<SkinWeapon>>, SkinsError> {
     let conn = get_connection()?;
-    let mut stmt = conn.prepare(LEVEL_LOOKUP_SQL).map_err(SkinsError::from)?;
+    let mut stmt = conn.prepare_cached(LEVEL_LOOKUP_SQL).map_err(SkinsError::from)?;
 
     level_uuids
         .iter()
@@ -247,6 +411,39 @@ pub fn get_skins_by_level_uuids(
         .collect()
 }
 
+// Matches weapons whose own UUID or any level UUID starts with the prefix.
+// `?1` must already end in `%` and contain only UUID characters.
+const PREFIX_LOOKUP_SQL: &str =
+    "SELECT w.uuid, w.displayName, w.displayIcon, w.tierUuid,
+            t.color, t.rank, t.displayIcon, t.displayName
+     FROM weapons w
+     LEFT JOIN tiers t ON w.tierUuid = t.uuid
+     WHERE w.uuid LIKE ?1
+        OR w.uuid IN (SELECT weaponUuid FROM levels WHERE uuid LIKE ?1)
+     ORDER BY w.displayName
+     LIMIT ?2";
+
+/// Find skins whose weapon or level UUID starts with `prefix`, at most `limit` of them.
+///
+/// The prefix is matched case-insensitively and must consist of hex digits and dashes.
+pub fn find_skins_by_uuid_prefix(prefix: &str, limit: usize) -> Result<Vec<SkinWeapon>, SkinsError> {
+    if !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Err(SkinsError::Database(format!("Invalid UUID prefix: {}", prefix)));
+    }
+
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare_cached(PREFIX_LOOKUP_SQL).map_err(SkinsError::from)?;
+    let pattern = format!("{}%", prefix.to_lowercase());
+
+    let skins = stmt
+        .query_map((pattern, limit as i64), map_skin_weapon_row)
+        .map_err(SkinsError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(SkinsError::from)?;
+
+    Ok(skins)
+}
+
 // -- Buddies ------------------------------------------------------------------
 
 pub(super) fn insert_buddies(buddies: &[BuddyApiEntry]) -> Result<(), SkinsError> {
@@ -327,7 +524,7 @@ const BUDDY_LOOKUP_SQL: &str =
 pub fn get_buddy_by_level_uuid(level_uuid: &str) -> Result<Option<BuddyItem>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(BUDDY_LOOKUP_SQL)
+        .prepare_cached(BUDDY_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     stmt.query_row([level_uuid], map_buddy_item_row)
@@ -340,7 +537,7 @@ pub fn get_buddies_by_level_uuids(
 ) -> Result<Vec<Option<BuddyItem>>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(BUDDY_LOOKUP_SQL)
+        .prepare_cached(BUDDY_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     level_uuids
@@ -381,10 +578,13 @@ fn map_flex_item_row(row: &rusqlite::Row) -> rusqlite::Result<FlexItem> {
     })
 }
 
+const FLEX_LOOKUP_SQL: &str =
+    "SELECT uuid, displayName, displayIcon, assetPath FROM flex WHERE uuid = ?1";
+
 pub fn get_flex_by_uuid(uuid: &str) -> Result<Option<FlexItem>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare("SELECT uuid, displayName, displayIcon, assetPath FROM flex WHERE uuid = ?1")
+        .prepare_cached(FLEX_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     stmt.query_row([uuid], map_flex_item_row)
@@ -395,7 +595,7 @@ pub fn get_flex_by_uuid(uuid: &str) -> Result<Option<FlexItem>, SkinsError> {
 pub fn get_flex_by_uuids(uuids: &[String]) -> Result<Vec<Option<FlexItem>>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare("SELECT uuid, displayName, displayIcon, assetPath FROM flex WHERE uuid = ?1")
+        .prepare_cached(FLEX_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     uuids
@@ -455,7 +655,7 @@ const PLAYERCARD_LOOKUP_SQL: &str =
 pub fn get_playercard_by_uuid(uuid: &str) -> Result<Option<PlayercardItem>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(PLAYERCARD_LOOKUP_SQL)
+        .prepare_cached(PLAYERCARD_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     stmt.query_row([uuid], map_playercard_item_row)
@@ -468,7 +668,7 @@ pub fn get_playercards_by_uuids(
 ) -> Result<Vec<Option<PlayercardItem>>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(PLAYERCARD_LOOKUP_SQL)
+        .prepare_cached(PLAYERCARD_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     uuids
@@ -573,7 +773,7 @@ const SPRAY_LOOKUP_SQL: &str =
 pub fn get_spray_by_level_uuid(level_uuid: &str) -> Result<Option<SprayItem>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(SPRAY_LOOKUP_SQL)
+        .prepare_cached(SPRAY_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     stmt.query_row([level_uuid], map_spray_item_row)
@@ -586,7 +786,7 @@ pub fn get_sprays_by_level_uuids(
 ) -> Result<Vec<Option<SprayItem>>, SkinsError> {
     let conn = get_connection()?;
     let mut stmt = conn
-        .prepare(SPRAY_LOOKUP_SQL)
+        .prepare_cached(SPRAY_LOOKUP_SQL)
         .map_err(SkinsError::from)?;
 
     level_uuids
@@ -597,4 +797,226 @@ pub fn get_sprays_by_level_uuids(
                 .map_err(SkinsError::from)
         })
         .collect()
-}
\ No newline at end of file
+}
+
+// -- Titles -------------------------------------------------------------------
+
+pub(super) fn insert_titles(titles: &[TitleApiEntry]) -> Result<(), SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "INSERT OR REPLACE INTO titles (uuid, displayName, titleText, assetPath) \
+             VALUES (?1, ?2, ?3, ?4)",
+        )
+        .map_err(SkinsError::from)?;
+
+    for title in titles {
+        stmt.execute((&title.uuid, &title.display_name, &title.title_text, &title.asset_path))
+            .map_err(SkinsError::from)?;
+    }
+
+    Ok(())
+}
+
+fn map_title_item_row(row: &rusqlite::Row) -> rusqlite::Result<TitleItem> {
+    Ok(TitleItem {
+        uuid: row.get(0)?,
+        display_name: row.get(1)?,
+        title_text: row.get(2)?,
+        asset_path: row.get(3)?,
+    })
+}
+
+const TITLE_LOOKUP_SQL: &str =
+    "SELECT uuid, displayName, titleText, assetPath FROM titles WHERE uuid = ?1";
+
+pub fn get_title_by_uuid(uuid: &str) -> Result<Option<TitleItem>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare_cached(TITLE_LOOKUP_SQL)
+        .map_err(SkinsError::from)?;
+
+    stmt.query_row([uuid], map_title_item_row)
+        .optional()
+        .map_err(SkinsError::from)
+}
+
+pub fn get_titles_by_uuids(uuids: &[String]) -> Result<Vec<Option<TitleItem>>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare_cached(TITLE_LOOKUP_SQL)
+        .map_err(SkinsError::from)?;
+
+    uuids
+        .iter()
+        .map(|uuid| {
+            stmt.query_row([uuid.as_str()], map_title_item_row)
+                .optional()
+                .map_err(SkinsError::from)
+        })
+        .collect()
+}
+
+// -- Agents -------------------------------------------------------------------
+
+pub(super) fn insert_agents(agents: &[AgentApiEntry]) -> Result<(), SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "INSERT OR REPLACE INTO agents (uuid, displayName, displayIcon, isBaseContent) \
+             VALUES (?1, ?2, ?3, ?4)",
+        )
+        .map_err(SkinsError::from)?;
+
+    for agent in agents {
+        stmt.execute((
+            &agent.uuid,
+            &agent.display_name,
+            &agent.display_icon,
+            agent.is_base_content,
+        ))
+        .map_err(SkinsError::from)?;
+    }
+
+    Ok(())
+}
+
+fn map_agent_item_row(row: &rusqlite::Row) -> rusqlite::Result<AgentItem> {
+    Ok(AgentItem {
+        uuid: row.get(0)?,
+        display_name: row.get(1)?,
+        display_icon: row.get(2)?,
+        is_base_content: row.get(3)?,
+    })
+}
+
+// Entitlement ItemIDs may differ in case from valorant-api.com UUIDs.
+const AGENT_LOOKUP_SQL: &str =
+    "SELECT uuid, displayName, displayIcon, isBaseContent FROM agents \
+     WHERE uuid = ?1 COLLATE NOCASE";
+
+pub fn get_agents_by_uuids(uuids: &[String]) -> Result<Vec<Option<AgentItem>>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare_cached(AGENT_LOOKUP_SQL)
+        .map_err(SkinsError::from)?;
+
+    uuids
+        .iter()
+        .map(|uuid| {
+            stmt.query_row([uuid.as_str()], map_agent_item_row)
+                .optional()
+                .map_err(SkinsError::from)
+        })
+        .collect()
+}
+
+/// Agents every account owns from the start, sorted by name.
+pub fn get_base_agents() -> Result<Vec<AgentItem>, SkinsError> {
+    let conn = get_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT uuid, displayName, displayIcon, isBaseContent FROM agents \
+             WHERE isBaseContent = 1 ORDER BY displayName",
+        )
+        .map_err(SkinsError::from)?;
+
+    let agents = stmt
+        .query_map([], map_agent_item_row)
+        .map_err(SkinsError::from)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(SkinsError::from)?;
+
+    Ok(agents)
+}
+
+// -- Any item type ------------------------------------------------------------
+
+// Lookup order matters: skins are by far the most common shop item, and titles
+// are checked last since they are the newest table.
+fn resolve_item_with(conn: &Connection, uuid: &str) -> Result<Option<ResolvedItem>, SkinsError> {
+    if let Some(skin) = conn
+        .query_row(LEVEL_LOOKUP_SQL, [uuid], map_skin_weapon_row)
+        .optional()
+        .map_err(SkinsError::from)?
+    {
+        return Ok(Some(ResolvedItem::Skin(skin)));
+    }
+
+    if let Some(buddy) = conn
+        .query_row(BUDDY_LOOKUP_SQL, [uuid], map_buddy_item_row)
+        .optional()
+        .map_err(SkinsError::from)?
+    {
+        return Ok(Some(ResolvedItem::Buddy(buddy)));
+    }
+
+    if let Some(spray) = conn
+        .query_row(SPRAY_LOOKUP_SQL, [uuid], map_spray_item_row)
+        .optional()
+        .map_err(SkinsError::from)?
+    {
+        return Ok(Some(ResolvedItem::Spray(spray)));
+    }
+
+    if let Some(flex) = conn
+        .query_row(FLEX_LOOKUP_SQL, [uuid], map_flex_item_row)
+        .optional()
+        .map_err(SkinsError::from)?
+    {
+        return Ok(Some(ResolvedItem::Flex(flex)));
+    }
+
+    if let Some(card) = conn
+        .query_row(PLAYERCARD_LOOKUP_SQL, [uuid], map_playercard_item_row)
+        .optional()
+        .map_err(SkinsError::from)?
+    {
+        return Ok(Some(ResolvedItem::Playercard(card)));
+    }
+
+    let title = conn
+        .query_row(TITLE_LOOKUP_SQL, [uuid], map_title_item_row)
+        .optional()
+        .map_err(SkinsError::from)?;
+
+    Ok(title.map(ResolvedItem::Title))
+}
+
+/// Resolve a UUID of unknown cosmetic type to the first matching item.
+pub fn resolve_item(uuid: &str) -> Result<Option<ResolvedItem>, SkinsError> {
+    let conn = get_connection()?;
+    resolve_item_with(&conn, uuid)
+}
+
+pub fn resolve_items(uuids: &[String]) -> Result<Vec<Option<ResolvedItem>>, SkinsError> {
+    let conn = get_connection()?;
+    uuids
+        .iter()
+        .map(|uuid| resolve_item_with(&conn, uuid))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookups_share_one_connection() {
+        let dir = tempfile::tempdir().unwrap();
+        initialize_skins_db(Some(dir.path().join("skins.db"))).unwrap();
+        let opened_before = CONNECTIONS_OPENED.load(Ordering::Relaxed);
+
+        let uuids: Vec<String> = (0..200)
+            .map(|i| format!("{:08x}-0000-0000-0000-000000000000", i))
+            .collect();
+        for uuid in &uuids {
+            assert!(get_skin_by_level_uuid(uuid).unwrap().is_none());
+        }
+        assert!(get_skins_by_level_uuids(&uuids).unwrap().iter().all(Option::is_none));
+
+        assert_eq!(CONNECTIONS_OPENED.load(Ordering::Relaxed) - opened_before, 1);
+        // Release the file so the temp dir can be removed
+        close_shared_connection();
+    }
+}
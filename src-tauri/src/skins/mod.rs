@@ -2,12 +2,20 @@ mod api;
 mod db;
 mod error;
 mod models;
+mod price;
 
-pub use api::sync_skins_database;
+pub use api::{cancel_sync, sync_empty_tables, sync_skins_database, SyncReport};
 pub use db::{
+    find_skins_by_uuid_prefix, get_agents_by_uuids, get_all_content_tiers, get_base_agents,
     get_buddies_by_level_uuids, get_buddy_by_level_uuid, get_flex_by_uuid, get_flex_by_uuids,
-    get_playercard_by_uuid, get_playercards_by_uuids, get_skin_by_level_uuid,
-    get_skins_by_level_uuids, get_spray_by_level_uuid, get_sprays_by_level_uuids,
-    initialize_skins_db,
+    get_playercard_by_uuid, get_playercards_by_uuids, get_skin_by_level_uuid, get_skin_by_uuid,
+    get_skins_by_level_uuids, get_skins_by_uuids, get_spray_by_level_uuid,
+    get_sprays_by_level_uuids, get_table_status, get_title_by_uuid, get_titles_by_uuids,
+    get_version_history, initialize_skins_db, move_skins_db, resolve_item, resolve_items,
+    TableStatus,
 };
-pub use models::{BuddyItem, FlexItem, PlayercardItem, SkinWeapon, SprayItem};
+pub use models::{
+    AgentItem, BuddyItem, ContentTier, FlexItem, PlayercardItem, ResolvedItem, SkinWeapon,
+    SprayItem, TitleItem, VersionHistoryEntry,
+};
+pub use price::{collection_value, estimated_price_for_tier, CollectionValue};
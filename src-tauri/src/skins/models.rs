@@ -15,6 +15,8 @@ pub(super) struct ContentTierApiEntry {
     pub(super) highlight_color: Option<String>,
     #[serde(rename = "displayIcon")]
     pub(super) display_icon: Option<String>,
+    #[serde(rename = "displayName")]
+    pub(super) display_name: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -178,6 +180,43 @@ pub(super) struct SprayLevelApiEntry {
     pub(super) asset_path: Option<String>,
 }
 
+// -- Player titles API types --------------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct TitlesApiResponse {
+    pub(super) data: Vec<TitleApiEntry>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TitleApiEntry {
+    pub(super) uuid: String,
+    #[serde(rename = "displayName")]
+    pub(super) display_name: Option<String>,
+    #[serde(rename = "titleText")]
+    pub(super) title_text: Option<String>,
+    #[serde(rename = "assetPath")]
+    pub(super) asset_path: Option<String>,
+}
+
+// -- Agents API types ---------------------------------------------------------
+
+#[derive(Deserialize)]
+pub(super) struct AgentsApiResponse {
+    pub(super) data: Vec<AgentApiEntry>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AgentApiEntry {
+    pub(super) uuid: String,
+    #[serde(rename = "displayName")]
+    pub(super) display_name: String,
+    #[serde(rename = "displayIcon")]
+    pub(super) display_icon: Option<String>,
+    /// Starter agents every account owns without an entitlement.
+    #[serde(rename = "isBaseContent", default)]
+    pub(super) is_base_content: bool,
+}
+
 // -- Public query result types ------------------------------------------------
 
 #[derive(Debug, Clone, Serialize)]
@@ -189,6 +228,24 @@ pub struct SkinWeapon {
     pub tier_color: Option<String>,
     pub tier_rank: Option<i32>,
     pub tier_icon: Option<String>,
+    pub tier_name: Option<String>,
+}
+
+/// A content tier (Select, Deluxe, Premium, Exclusive, Ultra).
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentTier {
+    pub uuid: String,
+    pub display_name: Option<String>,
+    pub rank: Option<i32>,
+    pub color: Option<String>,
+    pub display_icon: Option<String>,
+}
+
+/// A version the skins database was synced to, with the sync time in Unix seconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionHistoryEntry {
+    pub version: String,
+    pub synced_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -231,3 +288,67 @@ pub struct SprayItem {
     pub level_uuid: String,
     pub spray_level: Option<i32>,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TitleItem {
+    pub uuid: String,
+    pub display_name: Option<String>,
+    pub title_text: Option<String>,
+    pub asset_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentItem {
+    pub uuid: String,
+    pub display_name: String,
+    pub display_icon: Option<String>,
+    pub is_base_content: bool,
+}
+
+/// The first cosmetic matching a UUID, tagged with its item type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "item_type", content = "item", rename_all = "snake_case")]
+pub enum ResolvedItem {
+    Skin(SkinWeapon),
+    Buddy(BuddyItem),
+    Spray(SprayItem),
+    Flex(FlexItem),
+    Playercard(PlayercardItem),
+    Title(TitleItem),
+}
+
+impl ResolvedItem {
+    pub fn display_name(&self) -> Option<&str> {
+        match self {
+            ResolvedItem::Skin(s) => Some(&s.display_name),
+            ResolvedItem::Buddy(b) => Some(&b.display_name),
+            ResolvedItem::Spray(s) => Some(&s.display_name),
+            ResolvedItem::Flex(f) => Some(&f.display_name),
+            ResolvedItem::Playercard(p) => Some(&p.display_name),
+            ResolvedItem::Title(t) => t.title_text.as_deref().or(t.display_name.as_deref()),
+        }
+    }
+
+    pub fn display_icon(&self) -> Option<&str> {
+        match self {
+            ResolvedItem::Skin(s) => s.display_icon.as_deref(),
+            ResolvedItem::Buddy(b) => b.display_icon.as_deref(),
+            ResolvedItem::Spray(s) => s.display_icon.as_deref(),
+            ResolvedItem::Flex(f) => f.display_icon.as_deref(),
+            ResolvedItem::Playercard(p) => p.display_icon.as_deref(),
+            ResolvedItem::Title(_) => None,
+        }
+    }
+
+    /// The serialized `item_type` tag of this item.
+    pub fn item_type(&self) -> &'static str {
+        match self {
+            ResolvedItem::Skin(_) => "skin",
+            ResolvedItem::Buddy(_) => "buddy",
+            ResolvedItem::Spray(_) => "spray",
+            ResolvedItem::Flex(_) => "flex",
+            ResolvedItem::Playercard(_) => "playercard",
+            ResolvedItem::Title(_) => "title",
+        }
+    }
+}
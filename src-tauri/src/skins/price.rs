@@ -0,0 +1,118 @@
+use serde::Serialize;
+
+/// Canonical VP price for each content tier, keyed by valorant-api `contenttiers.uuid`.
+///
+/// Keyed by UUID rather than `rank`, whose numbering is easy to get wrong
+/// (Exclusive is rank 3, Ultra rank 4). Exclusive has no single price and is
+/// left out. Melee skins usually cost about twice their tier price, which this
+/// table does not model.
+const TIER_PRICES: &[(&str, u64)] = &[
+    ("12683d76-48d7-84a3-4e09-6985794f0445", 875),  // Select
+    ("0cebb8be-46d7-c12a-d306-e9907bfc5a25", 1275), // Deluxe
+    ("60bca009-4182-7998-dee7-b8a2558dc369", 1775), // Premium
+    ("411e4a55-4e59-7757-41f0-86a53f101bb5", 2475), // Ultra
+];
+
+/// Estimated VP price for a skin of the given content tier UUID.
+///
+/// This is a guide for skins not currently in any shop, not the live cost.
+pub fn estimated_price_for_tier(tier_uuid: &str) -> Option<u64> {
+    TIER_PRICES
+        .iter()
+        .find(|(uuid, _)| uuid.eq_ignore_ascii_case(tier_uuid))
+        .map(|(_, price)| *price)
+}
+
+/// Estimated value of the skins of one tier within a collection.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TierValue {
+    pub tier_uuid: String,
+    pub count: u32,
+    pub total_vp: u64,
+}
+
+/// Estimated VP value of a list of skins, grouped by tier.
+///
+/// Skins that are unknown, untiered, or in a tier without a fixed price are
+/// counted in `excluded_count` instead of the total.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CollectionValue {
+    pub total_vp: u64,
+    /// Cheapest tier first.
+    pub tiers: Vec<TierValue>,
+    pub excluded_count: u32,
+}
+
+/// Sum the estimated prices for skins with the given tier UUIDs (`None` = unknown skin).
+pub fn collection_value(tier_uuids: &[Option<String>]) -> CollectionValue {
+    let mut tiers: Vec<TierValue> = Vec::new();
+    let mut excluded_count = 0;
+
+    for tier_uuid in tier_uuids {
+        let Some((tier_uuid, price)) = tier_uuid
+            .as_deref()
+            .and_then(|uuid| estimated_price_for_tier(uuid).map(|p| (uuid.to_lowercase(), p)))
+        else {
+            excluded_count += 1;
+            continue;
+        };
+
+        match tiers.iter_mut().find(|t| t.tier_uuid == tier_uuid) {
+            Some(tier) => {
+                tier.count += 1;
+                tier.total_vp += price;
+            }
+            None => tiers.push(TierValue {
+                tier_uuid,
+                count: 1,
+                total_vp: price,
+            }),
+        }
+    }
+
+    tiers.sort_by_key(|t| estimated_price_for_tier(&t.tier_uuid));
+
+    CollectionValue {
+        total_vp: tiers.iter().map(|t| t.total_vp).sum(),
+        tiers,
+        excluded_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELECT: &str = "12683d76-48d7-84a3-4e09-6985794f0445";
+    const PREMIUM: &str = "60bca009-4182-7998-dee7-b8a2558dc369";
+    const EXCLUSIVE: &str = "e046854e-406c-37f4-6607-19a9ba8426fc";
+    const ULTRA: &str = "411e4a55-4e59-7757-41f0-86a53f101bb5";
+
+    #[test]
+    fn test_collection_value_groups_by_tier() {
+        let value = collection_value(&[
+            Some(PREMIUM.to_uppercase()),
+            Some(SELECT.to_string()),
+            Some(PREMIUM.to_string()),
+            Some(EXCLUSIVE.to_string()),
+            None,
+        ]);
+
+        assert_eq!(value.total_vp, 875 + 1775 * 2);
+        assert_eq!(value.excluded_count, 2);
+        assert_eq!(
+            value.tiers,
+            vec![
+                TierValue { tier_uuid: SELECT.to_string(), count: 1, total_vp: 875 },
+                TierValue { tier_uuid: PREMIUM.to_string(), count: 2, total_vp: 3550 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_estimated_price_for_tier() {
+        assert_eq!(estimated_price_for_tier(SELECT), Some(875));
+        assert_eq!(estimated_price_for_tier(ULTRA), Some(2475));
+        assert_eq!(estimated_price_for_tier(EXCLUSIVE), None);
+    }
+}
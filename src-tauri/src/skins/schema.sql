@@ -5,11 +5,19 @@ CREATE TABLE IF NOT EXISTS info (
 );
 INSERT OR IGNORE INTO info (rowid, version) VALUES (1, NULL);
 
+-- One row per successful sync, oldest first
+CREATE TABLE IF NOT EXISTS version_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    version TEXT NOT NULL,
+    synced_at INTEGER NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS tiers (
     uuid TEXT PRIMARY KEY,
     color TEXT,
     rank INTEGER,
-    displayIcon TEXT
+    displayIcon TEXT,
+    displayName TEXT
 );
 
 CREATE TABLE IF NOT EXISTS weapons (
@@ -98,3 +106,17 @@ CREATE TABLE IF NOT EXISTS spray_levels (
 );
 
 CREATE INDEX IF NOT EXISTS idx_spray_levels_spray ON spray_levels(sprayUuid);
+
+CREATE TABLE IF NOT EXISTS titles (
+    uuid TEXT PRIMARY KEY,
+    displayName TEXT,
+    titleText TEXT,
+    assetPath TEXT
+);
+
+CREATE TABLE IF NOT EXISTS agents (
+    uuid TEXT PRIMARY KEY,
+    displayName TEXT NOT NULL,
+    displayIcon TEXT,
+    isBaseContent INTEGER NOT NULL DEFAULT 0
+);
@@ -119,15 +119,33 @@ export interface Bundle {
   items: BundleItem[]
 }
 
+export interface AccessoryOffer {
+  item_uuid: string
+  item_type_id: string
+  cost: number
+  currency_id: string
+}
+
 export interface Storefront {
   bundles?: Bundle[]
   daily_offers: DailyOffer[]
   daily_remaining_secs: number
   night_market: NightMarketOffer[] | null
   night_market_remaining_secs: number | null
+  /** UNIX seconds when the daily offers rotate */
+  daily_expires_at: number
+  /** UNIX seconds when the night market closes */
+  night_market_expires_at: number | null
+  accessory_store: AccessoryOffer[] | null
+  accessory_remaining_secs: number | null
 }
 
-export async function getShop(accountId: number, cookies: RiotCookies): Promise<Storefront> {
+export interface ShopResponse extends Storefront {
+  /** `skin_uuid` of each daily offer on the account's wishlist */
+  wishlist_hits: string[]
+}
+
+export async function getShop(accountId: number, cookies: RiotCookies): Promise<ShopResponse> {
   return invoke('get_shop', { accountId, cookies })
 }
 
@@ -139,6 +157,7 @@ export interface SkinWeapon {
   tier_color: string | null
   tier_rank: number | null
   tier_icon: string | null
+  tier_name: string | null
 }
 
 export async function getSkinInfo(levelUuid: string): Promise<SkinWeapon | null> {
@@ -202,7 +221,13 @@ export async function getFlexInfoBatch(uuids: string[]): Promise<(FlexItem | nul
   return invoke('get_flex_info_batch', { uuids })
 }
 
-export async function syncSkins(): Promise<boolean> {
+export interface SyncReport {
+  updated: string[]
+  failed: [string, string][]
+  cancelled: boolean
+}
+
+export async function syncSkins(): Promise<SyncReport> {
   return invoke('sync_skins')
 }
 
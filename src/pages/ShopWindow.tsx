@@ -108,27 +108,27 @@ function formatVp(vp: number): string {
 
 const MOCK_SKIN_MAP: Record<string, SkinWeapon> = {
   // Bundle: Spectrum (5 items)
-  'mock-sp-1': { uuid: 'mock-sp-1', display_name: 'Spectrum Phantom', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-sp-2': { uuid: 'mock-sp-2', display_name: 'Spectrum Vandal', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-sp-3': { uuid: 'mock-sp-3', display_name: 'Spectrum Operator', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-sp-4': { uuid: 'mock-sp-4', display_name: 'Spectrum Sheriff', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-sp-5': { uuid: 'mock-sp-5', display_name: 'Spectrum Knife', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null },
+  'mock-sp-1': { uuid: 'mock-sp-1', display_name: 'Spectrum Phantom', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-sp-2': { uuid: 'mock-sp-2', display_name: 'Spectrum Vandal', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-sp-3': { uuid: 'mock-sp-3', display_name: 'Spectrum Operator', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-sp-4': { uuid: 'mock-sp-4', display_name: 'Spectrum Sheriff', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-sp-5': { uuid: 'mock-sp-5', display_name: 'Spectrum Knife', display_icon: null, tier_color: '0096FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
   // Bundle: Ruination (3 items)
-  'mock-ru-1': { uuid: 'mock-ru-1', display_name: 'Ruination Phantom', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-ru-2': { uuid: 'mock-ru-2', display_name: 'Ruination Vandal', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-ru-3': { uuid: 'mock-ru-3', display_name: 'Ruination Knife', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null },
+  'mock-ru-1': { uuid: 'mock-ru-1', display_name: 'Ruination Phantom', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-ru-2': { uuid: 'mock-ru-2', display_name: 'Ruination Vandal', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-ru-3': { uuid: 'mock-ru-3', display_name: 'Ruination Knife', display_icon: null, tier_color: '9147FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
   // Daily (4 items)
-  'mock-a': { uuid: 'mock-a', display_name: 'DEMO Phantom', display_icon: null, tier_color: 'FF4655', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-b': { uuid: 'mock-b', display_name: 'DEMO Vandal', display_icon: null, tier_color: '009BDE', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-c': { uuid: 'mock-c', display_name: 'DEMO Operator', display_icon: null, tier_color: 'F5A623', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-d': { uuid: 'mock-d', display_name: 'DEMO Knife', display_icon: null, tier_color: 'BD3944', tier_uuid: null, tier_rank: null, tier_icon: null },
+  'mock-a': { uuid: 'mock-a', display_name: 'DEMO Phantom', display_icon: null, tier_color: 'FF4655', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-b': { uuid: 'mock-b', display_name: 'DEMO Vandal', display_icon: null, tier_color: '009BDE', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-c': { uuid: 'mock-c', display_name: 'DEMO Operator', display_icon: null, tier_color: 'F5A623', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-d': { uuid: 'mock-d', display_name: 'DEMO Knife', display_icon: null, tier_color: 'BD3944', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
   // Nightmarket (6 items)
-  'mock-nm-1': { uuid: 'mock-nm-1', display_name: 'Prime Phantom', display_icon: null, tier_color: 'F0C75E', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-nm-2': { uuid: 'mock-nm-2', display_name: 'Ion Vandal', display_icon: null, tier_color: '5CFFCB', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-nm-3': { uuid: 'mock-nm-3', display_name: 'Elderflame Operator', display_icon: null, tier_color: 'FF6B35', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-nm-4': { uuid: 'mock-nm-4', display_name: 'Glitchpop Frenzy', display_icon: null, tier_color: 'FF00FF', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-nm-5': { uuid: 'mock-nm-5', display_name: 'Reaver Sheriff', display_icon: null, tier_color: 'E74C3C', tier_uuid: null, tier_rank: null, tier_icon: null },
-  'mock-nm-6': { uuid: 'mock-nm-6', display_name: 'Origin Guardian', display_icon: null, tier_color: '7B68EE', tier_uuid: null, tier_rank: null, tier_icon: null },
+  'mock-nm-1': { uuid: 'mock-nm-1', display_name: 'Prime Phantom', display_icon: null, tier_color: 'F0C75E', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-nm-2': { uuid: 'mock-nm-2', display_name: 'Ion Vandal', display_icon: null, tier_color: '5CFFCB', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-nm-3': { uuid: 'mock-nm-3', display_name: 'Elderflame Operator', display_icon: null, tier_color: 'FF6B35', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-nm-4': { uuid: 'mock-nm-4', display_name: 'Glitchpop Frenzy', display_icon: null, tier_color: 'FF00FF', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-nm-5': { uuid: 'mock-nm-5', display_name: 'Reaver Sheriff', display_icon: null, tier_color: 'E74C3C', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
+  'mock-nm-6': { uuid: 'mock-nm-6', display_name: 'Origin Guardian', display_icon: null, tier_color: '7B68EE', tier_uuid: null, tier_rank: null, tier_icon: null, tier_name: null },
 }
 
 const MOCK_STOREFRONT: Storefront = {
@@ -179,6 +179,10 @@ const MOCK_STOREFRONT: Storefront = {
     { skin_uuid: 'mock-nm-6', base_cost: 1775, discount_cost: 1243, discount_percent: 30 },
   ],
   night_market_remaining_secs: 3600 * 24 * 5,
+  daily_expires_at: Math.floor(Date.now() / 1000) + 3600 * 8,
+  night_market_expires_at: Math.floor(Date.now() / 1000) + 3600 * 24 * 5,
+  accessory_store: null,
+  accessory_remaining_secs: null,
 }
 
 // --- Components ---
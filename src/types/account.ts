@@ -7,6 +7,10 @@ export interface Account {
   rank: string | null
   is_active: boolean
   data_folder: string | null
+  data_path_override: string | null
+  region: string | null
+  is_favorite: boolean
+  launch_args: string | null
   created_at: string
   updated_at: string
 }
@@ -18,6 +22,7 @@ export interface CreateAccount {
   password: string | null
   rank: string | null
   use_current_data: boolean
+  launch_args?: string | null
 }
 
 export interface UpdateAccount {
@@ -27,6 +32,7 @@ export interface UpdateAccount {
   username: string | null
   password: string | null
   rank: string | null
+  launch_args?: string | null
 }
 
 export const VALORANT_RANKS = [
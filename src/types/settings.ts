@@ -1,12 +1,24 @@
 export interface Settings {
   id: number
   active_account_id: number | null
+  previous_account_id: number | null
   riot_client_service_path: string | null
   riot_client_data_path: string | null
   account_data_path: string | null
   henrikdev_api_key: string | null
   region: string | null
   launched: boolean
+  auto_select_last_account: boolean
+  prefetch_shops_on_startup: boolean
+  show_vp_costs: boolean
+  encryption_mode: 'machine' | 'master_password'
+  auto_lock_minutes: number | null
+  theme: string | null
+  accent_color: string | null
+  compress_history: boolean
+  bundle_discount_alert_threshold: number | null
+  process_poll_secs: number
+  extra_watched_processes: string[]
   created_at: string
   updated_at: string
 }
@@ -17,4 +29,15 @@ export interface UpdateSettings {
   account_data_path?: string | null
   henrikdev_api_key?: string | null
   region?: string | null
+  auto_select_last_account?: boolean | null
+  prefetch_shops_on_startup?: boolean | null
+  show_vp_costs?: boolean | null
+  auto_lock_minutes?: number | null
+  theme?: string | null
+  accent_color?: string | null
+  compress_history?: boolean | null
+  bundle_discount_alert_threshold?: number | null
+  clear_bundle_discount_alert_threshold?: boolean
+  process_poll_secs?: number | null
+  extra_watched_processes?: string[] | null
 }